@@ -25,57 +25,91 @@ Register-ArgumentCompleter -Native -CommandName 'mcup' -ScriptBlock {
             [CompletionResult]::new('--groups', '--groups', [CompletionResultType]::ParameterName, 'Selects artifacts based on the group ID. Subgroups are included by default.')
             [CompletionResult]::new('-a', '-a', [CompletionResultType]::ParameterName, 'Selects artifacts based on the artifact ID. Supports globbing like in ''maven-*-plugin''.')
             [CompletionResult]::new('--artifacts', '--artifacts', [CompletionResultType]::ParameterName, 'Selects artifacts based on the artifact ID. Supports globbing like in ''maven-*-plugin''.')
-            [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Selects artifacts based on version (ranges). Use ''<n>..'' to select the n most recent versions, ''..<n>'' to select the n oldest versions and ''<version>'' to select one specific version only.')
-            [CompletionResult]::new('--versions', '--versions', [CompletionResultType]::ParameterName, 'Selects artifacts based on version (ranges). Use ''<n>..'' to select the n most recent versions, ''..<n>'' to select the n oldest versions and ''<version>'' to select one specific version only.')
-            [CompletionResult]::new('-l', '-l', [CompletionResultType]::ParameterName, 'Sets the location of the local maven repository. Respects the directory configured in ''~/.m2/settings.xml''. Falls back to ''~/.m2/repository'', if nothing has been specified or configured.')
-            [CompletionResult]::new('--local-repository', '--local-repository', [CompletionResultType]::ParameterName, 'Sets the location of the local maven repository. Respects the directory configured in ''~/.m2/settings.xml''. Falls back to ''~/.m2/repository'', if nothing has been specified or configured.')
+            [CompletionResult]::new('-v', '-v', [CompletionResultType]::ParameterName, 'Selects artifacts based on version (ranges). Use ''<n>..'' to select the n most recent versions, ''..<n>'' to select the n oldest versions and ''<version>'' to select one specific version only. Use ''latest-per:major=<n>'' or ''latest-per:minor=<n>'' to select the n most recent versions of each major or minor line. Use ''since:<yyyy-mm-dd>'', ''until:<yyyy-mm-dd>'', ''age<<n>[d|w|m|y]'' or ''age><n>[d|w|m|y]'' to select versions by the time they''ve been downloaded or installed.')
+            [CompletionResult]::new('--versions', '--versions', [CompletionResultType]::ParameterName, 'Selects artifacts based on version (ranges). Use ''<n>..'' to select the n most recent versions, ''..<n>'' to select the n oldest versions and ''<version>'' to select one specific version only. Use ''latest-per:major=<n>'' or ''latest-per:minor=<n>'' to select the n most recent versions of each major or minor line. Use ''since:<yyyy-mm-dd>'', ''until:<yyyy-mm-dd>'', ''age<<n>[d|w|m|y]'' or ''age><n>[d|w|m|y]'' to select versions by the time they''ve been downloaded or installed.')
+            [CompletionResult]::new('-c', '-c', [CompletionResultType]::ParameterName, 'Selects files inside the versions based on a comma separated list of classifiers like in ''sources,javadoc''. Supports globbing. Only the selected files are analyzed or removed, not the whole version.')
+            [CompletionResult]::new('--classifiers', '--classifiers', [CompletionResultType]::ParameterName, 'Selects files inside the versions based on a comma separated list of classifiers like in ''sources,javadoc''. Supports globbing. Only the selected files are analyzed or removed, not the whole version.')
+            [CompletionResult]::new('--origin', '--origin', [CompletionResultType]::ParameterName, 'Selects versions based on their origin as recorded in ''_remote.repositories'': ''local'' for artifacts installed by ''mvn install'', ''remote'' for downloaded artifacts, or the ID of a remote repository like ''central''. Versions without ''_remote.repositories'' are considered local.')
+            [CompletionResult]::new('--repository-id', '--repository-id', [CompletionResultType]::ParameterName, 'Selects versions downloaded from the remote repositories with the given IDs like in ''central,jboss-*''. Supports globbing. The IDs are taken from ''_remote.repositories'' and ''maven-metadata-<repoId>.xml''.')
+            [CompletionResult]::new('-l', '-l', [CompletionResultType]::ParameterName, 'Sets the location of the local maven repository. Respects ''-Dmaven.repo.local'' in ''MAVEN_ARGS'' and ''MAVEN_OPTS'' and the directory configured in the user and global settings. Falls back to ''~/.m2/repository'', if nothing has been specified or configured.')
+            [CompletionResult]::new('--local-repository', '--local-repository', [CompletionResultType]::ParameterName, 'Sets the location of the local maven repository. Respects ''-Dmaven.repo.local'' in ''MAVEN_ARGS'' and ''MAVEN_OPTS'' and the directory configured in the user and global settings. Falls back to ''~/.m2/repository'', if nothing has been specified or configured.')
+            [CompletionResult]::new('--discover', '--discover', [CompletionResultType]::ParameterName, 'Runs the subcommand against all local repositories found below ROOT, e.g. the repositories of the workspaces on a CI host. Repositories are detected by the maven layout and the ''_remote.repositories'' markers. Gradle module caches are detected by ''modules-2/files-2.1'', ivy by ''.ivy2''.')
+            [CompletionResult]::new('--project', '--project', [CompletionResultType]::ParameterName, 'Uses the local repository configured with ''-Dmaven.repo.local'' in ''.mvn/maven.config'' or ''.mvn/jvm.config'' of another maven project. The project root is the first directory containing ''.mvn/'' in or above DIR. Defaults to the project in or above the current directory.')
+            [CompletionResult]::new('--settings', '--settings', [CompletionResultType]::ParameterName, 'Uses an alternate user settings file instead of ''~/.m2/settings.xml'' to locate the local repository')
+            [CompletionResult]::new('--global-settings', '--global-settings', [CompletionResultType]::ParameterName, 'Uses an alternate global settings file instead of ''$MAVEN_HOME/conf/settings.xml'' to locate the local repository')
+            [CompletionResult]::new('--gradle', '--gradle', [CompletionResultType]::ParameterName, 'Uses the module cache of gradle ''$GRADLE_USER_HOME/caches/modules-2'' (defaults to ''~/.gradle/caches/modules-2'') instead of the local maven repository. Module caches are also detected when passed to ''--local-repository''.')
+            [CompletionResult]::new('--ivy', '--ivy', [CompletionResultType]::ParameterName, 'Uses the cache and local repository of ivy in ''~/.ivy2'' (used by sbt and ant) instead of the local maven repository. ''~/.ivy2'', ''~/.ivy2/cache'' and ''~/.ivy2/local'' are also detected when passed to ''--local-repository''.')
             [CompletionResult]::new('-r', '-r', [CompletionResultType]::ParameterName, 'Selects released artifacts only')
             [CompletionResult]::new('--releases', '--releases', [CompletionResultType]::ParameterName, 'Selects released artifacts only')
             [CompletionResult]::new('-s', '-s', [CompletionResultType]::ParameterName, 'Selects snapshot artifacts only')
             [CompletionResult]::new('--snapshots', '--snapshots', [CompletionResultType]::ParameterName, 'Selects snapshot artifacts only')
+            [CompletionResult]::new('--installed', '--installed', [CompletionResultType]::ParameterName, 'Selects artifacts in the ''installed'' tree of a split local repository only')
+            [CompletionResult]::new('--cached', '--cached', [CompletionResultType]::ParameterName, 'Selects artifacts in the ''cached'' tree of a split local repository only')
+            [CompletionResult]::new('--opaque', '--opaque', [CompletionResultType]::ParameterName, 'Selects opaque versions only: version directories like ''latest'', ''RELEASE'', ''${revision}'' or commit hashes, which don''t follow the maven version scheme. Opaque versions are ordered below all other versions, but version ranges don''t count them. keep never removes them, unless this flag is given: Then keep removes the opaque versions not matched by the other filters and leaves all other versions alone.')
+            [CompletionResult]::new('-q', '-q', [CompletionResultType]::ParameterName, 'Prints neither progress nor the summary of keep and rm, only warnings and errors. Useful for cron jobs.')
+            [CompletionResult]::new('--quiet', '--quiet', [CompletionResultType]::ParameterName, 'Prints neither progress nor the summary of keep and rm, only warnings and errors. Useful for cron jobs.')
+            [CompletionResult]::new('-V', '-V ', [CompletionResultType]::ParameterName, 'Logs skipped directories, opaque versions and protected versions to stderr. Repeat (''-VV'') to log the decision of the filters for each version as well. The short flag is ''-V'', since ''-v'' is ''--versions''.')
+            [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Logs skipped directories, opaque versions and protected versions to stderr. Repeat (''-VV'') to log the decision of the filters for each version as well. The short flag is ''-V'', since ''-v'' is ''--versions''.')
+            [CompletionResult]::new('--version', '--version', [CompletionResultType]::ParameterName, 'Prints version')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('-V', '-V ', [CompletionResultType]::ParameterName, 'Print version')
-            [CompletionResult]::new('--version', '--version', [CompletionResultType]::ParameterName, 'Print version')
             [CompletionResult]::new('keep', 'keep', [CompletionResultType]::ParameterValue, 'Keeps the artifacts matched by the filters and removes the rest')
             [CompletionResult]::new('rm', 'rm', [CompletionResultType]::ParameterValue, 'Removes the artifacts matched by the filters and keeps the rest')
             [CompletionResult]::new('du', 'du', [CompletionResultType]::ParameterValue, 'Analyzes the size of the artifacts selected by the filters')
+            [CompletionResult]::new('versions', 'versions', [CompletionResultType]::ParameterValue, 'Lists the versions of an artifact in the order used by ''--versions'' together with their parsed components, type and size')
+            [CompletionResult]::new('version-compare', 'version-compare', [CompletionResultType]::ParameterValue, 'Shows how two versions are parsed and which one is greater')
             [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
             break
         }
         'mcup;keep' {
+            [CompletionResult]::new('--wait', '--wait', [CompletionResultType]::ParameterName, 'Waits up to the given timeout (like ''30s'', ''5m'' or ''1h'') while another process uses the repository. Fails immediately, if not specified.')
+            [CompletionResult]::new('--max-removal', '--max-removal', [CompletionResultType]::ParameterName, 'Refuses to remove more than the given percentage of the repository. Use 100 to disable the check.')
             [CompletionResult]::new('-d', '-d', [CompletionResultType]::ParameterName, 'Does not remove artifacts')
             [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Does not remove artifacts')
             [CompletionResult]::new('--list', '--list', [CompletionResultType]::ParameterName, 'Prints the full path to the artifacts that will be removed')
+            [CompletionResult]::new('-y', '-y', [CompletionResultType]::ParameterName, 'Does not ask for confirmation before removing artifacts')
+            [CompletionResult]::new('--yes', '--yes', [CompletionResultType]::ParameterName, 'Does not ask for confirmation before removing artifacts')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('-V', '-V ', [CompletionResultType]::ParameterName, 'Print version')
-            [CompletionResult]::new('--version', '--version', [CompletionResultType]::ParameterName, 'Print version')
             break
         }
         'mcup;rm' {
+            [CompletionResult]::new('--wait', '--wait', [CompletionResultType]::ParameterName, 'Waits up to the given timeout (like ''30s'', ''5m'' or ''1h'') while another process uses the repository. Fails immediately, if not specified.')
+            [CompletionResult]::new('--attachments', '--attachments', [CompletionResultType]::ParameterName, 'Removes only the given attachments (and their checksums) of the selected versions and leaves the main artifacts untouched')
             [CompletionResult]::new('-d', '-d', [CompletionResultType]::ParameterName, 'Does not remove artifacts')
             [CompletionResult]::new('--dry-run', '--dry-run', [CompletionResultType]::ParameterName, 'Does not remove artifacts')
             [CompletionResult]::new('--list', '--list', [CompletionResultType]::ParameterName, 'Prints the full path to the artifacts that will be removed')
+            [CompletionResult]::new('-y', '-y', [CompletionResultType]::ParameterName, 'Does not ask for confirmation before removing artifacts')
+            [CompletionResult]::new('--yes', '--yes', [CompletionResultType]::ParameterName, 'Does not ask for confirmation before removing artifacts')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('-V', '-V ', [CompletionResultType]::ParameterName, 'Print version')
-            [CompletionResult]::new('--version', '--version', [CompletionResultType]::ParameterName, 'Print version')
             break
         }
         'mcup;du' {
-            [CompletionResult]::new('-o', '-o', [CompletionResultType]::ParameterName, 'Defines whether (g)roups, (a)rtifacts and (v)ersions are included in the usage summary')
-            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Defines whether (g)roups, (a)rtifacts and (v)ersions are included in the usage summary')
+            [CompletionResult]::new('-o', '-o', [CompletionResultType]::ParameterName, 'Defines whether (o)rigins, (g)roups, (a)rtifacts, (v)ersions and (f)iles are included in the usage summary. Origins group the usage by the repositories the versions have been downloaded from.')
+            [CompletionResult]::new('--output', '--output', [CompletionResultType]::ParameterName, 'Defines whether (o)rigins, (g)roups, (a)rtifacts, (v)ersions and (f)iles are included in the usage summary. Origins group the usage by the repositories the versions have been downloaded from.')
+            [CompletionResult]::new('--apparent-size', '--apparent-size', [CompletionResultType]::ParameterName, 'Prints apparent sizes rather than disk usage. Like ''du --apparent-size'', this is the sum of the file lengths rather than the blocks allocated on disk.')
+            [CompletionResult]::new('--all', '--all', [CompletionResultType]::ParameterName, 'Includes files which are not part of a version (maven metadata, directories which are not a version, leftovers of failed downloads, ...) as ''(unaccounted)''')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'mcup;versions' {
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'mcup;version-compare' {
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
-            [CompletionResult]::new('-V', '-V ', [CompletionResultType]::ParameterName, 'Print version')
-            [CompletionResult]::new('--version', '--version', [CompletionResultType]::ParameterName, 'Print version')
             break
         }
         'mcup;help' {
             [CompletionResult]::new('keep', 'keep', [CompletionResultType]::ParameterValue, 'Keeps the artifacts matched by the filters and removes the rest')
             [CompletionResult]::new('rm', 'rm', [CompletionResultType]::ParameterValue, 'Removes the artifacts matched by the filters and keeps the rest')
             [CompletionResult]::new('du', 'du', [CompletionResultType]::ParameterValue, 'Analyzes the size of the artifacts selected by the filters')
+            [CompletionResult]::new('versions', 'versions', [CompletionResultType]::ParameterValue, 'Lists the versions of an artifact in the order used by ''--versions'' together with their parsed components, type and size')
+            [CompletionResult]::new('version-compare', 'version-compare', [CompletionResultType]::ParameterValue, 'Shows how two versions are parsed and which one is greater')
             [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
             break
         }
@@ -88,6 +122,12 @@ Register-ArgumentCompleter -Native -CommandName 'mcup' -ScriptBlock {
         'mcup;help;du' {
             break
         }
+        'mcup;help;versions' {
+            break
+        }
+        'mcup;help;version-compare' {
+            break
+        }
         'mcup;help;help' {
             break
         }
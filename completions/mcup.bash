@@ -28,6 +28,12 @@ _mcup() {
             mcup,rm)
                 cmd="mcup__subcmd__rm"
                 ;;
+            mcup,version-compare)
+                cmd="mcup__subcmd__version__subcmd__compare"
+                ;;
+            mcup,versions)
+                cmd="mcup__subcmd__versions"
+                ;;
             mcup__subcmd__help,du)
                 cmd="mcup__subcmd__help__subcmd__du"
                 ;;
@@ -40,6 +46,12 @@ _mcup() {
             mcup__subcmd__help,rm)
                 cmd="mcup__subcmd__help__subcmd__rm"
                 ;;
+            mcup__subcmd__help,version-compare)
+                cmd="mcup__subcmd__help__subcmd__version__subcmd__compare"
+                ;;
+            mcup__subcmd__help,versions)
+                cmd="mcup__subcmd__help__subcmd__versions"
+                ;;
             *)
                 ;;
         esac
@@ -47,7 +59,7 @@ _mcup() {
 
     case "${cmd}" in
         mcup)
-            opts="-g -a -v -l -r -s -h -V --groups --artifacts --versions --local-repository --releases --snapshots --help --version keep rm du help"
+            opts="-g -a -v -c -l -r -s -q -V -h --groups --artifacts --versions --classifiers --origin --repository-id --local-repository --gradle --ivy --discover --project --settings --global-settings --releases --snapshots --installed --cached --opaque --quiet --verbose --version --help keep rm du versions version-compare help"
             if [[ ${cur} == -* || ${COMP_CWORD} -eq 1 ]] ; then
                 COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
                 return 0
@@ -77,6 +89,22 @@ _mcup() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --classifiers)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                -c)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --origin)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --repository-id)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --local-repository)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -85,6 +113,22 @@ _mcup() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --discover)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --project)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --settings)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --global-settings)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 *)
                     COMPREPLY=()
                     ;;
@@ -93,7 +137,7 @@ _mcup() {
             return 0
             ;;
         mcup__subcmd__du)
-            opts="-o -h -V --output --help --version"
+            opts="-o -h --output --apparent-size --all --help"
             if [[ ${cur} == -* || ${COMP_CWORD} -eq 2 ]] ; then
                 COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
                 return 0
@@ -115,7 +159,7 @@ _mcup() {
             return 0
             ;;
         mcup__subcmd__help)
-            opts="keep rm du help"
+            opts="keep rm du versions version-compare help"
             if [[ ${cur} == -* || ${COMP_CWORD} -eq 2 ]] ; then
                 COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
                 return 0
@@ -184,13 +228,49 @@ _mcup() {
             COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
             return 0
             ;;
+        mcup__subcmd__help__subcmd__version__subcmd__compare)
+            opts=""
+            if [[ ${cur} == -* || ${COMP_CWORD} -eq 3 ]] ; then
+                COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                return 0
+            fi
+            case "${prev}" in
+                *)
+                    COMPREPLY=()
+                    ;;
+            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;
+        mcup__subcmd__help__subcmd__versions)
+            opts=""
+            if [[ ${cur} == -* || ${COMP_CWORD} -eq 3 ]] ; then
+                COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                return 0
+            fi
+            case "${prev}" in
+                *)
+                    COMPREPLY=()
+                    ;;
+            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;
         mcup__subcmd__keep)
-            opts="-d -h -V --dry-run --list --help --version"
+            opts="-d -y -h --dry-run --list --yes --wait --max-removal --help"
             if [[ ${cur} == -* || ${COMP_CWORD} -eq 2 ]] ; then
                 COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
                 return 0
             fi
             case "${prev}" in
+                --wait)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --max-removal)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 *)
                     COMPREPLY=()
                     ;;
@@ -199,7 +279,43 @@ _mcup() {
             return 0
             ;;
         mcup__subcmd__rm)
-            opts="-d -h -V --dry-run --list --help --version"
+            opts="-d -y -h --dry-run --list --yes --wait --attachments --help"
+            if [[ ${cur} == -* || ${COMP_CWORD} -eq 2 ]] ; then
+                COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                return 0
+            fi
+            case "${prev}" in
+                --wait)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --attachments)
+                    COMPREPLY=($(compgen -W "sources javadoc tests" -- "${cur}"))
+                    return 0
+                    ;;
+                *)
+                    COMPREPLY=()
+                    ;;
+            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;
+        mcup__subcmd__version__subcmd__compare)
+            opts="-h --help"
+            if [[ ${cur} == -* || ${COMP_CWORD} -eq 2 ]] ; then
+                COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                return 0
+            fi
+            case "${prev}" in
+                *)
+                    COMPREPLY=()
+                    ;;
+            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;
+        mcup__subcmd__versions)
+            opts="-h --help"
             if [[ ${cur} == -* || ${COMP_CWORD} -eq 2 ]] ; then
                 COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
                 return 0
@@ -1,53 +1,80 @@
 # Print an optspec for argparse to handle cmd's options that are independent of any subcommand.
 function __fish_mcup_global_optspecs
-	string join \n g/groups= a/artifacts= v/versions= l/local-repository= r/releases s/snapshots h/help V/version
+    string join \n g/groups= a/artifacts= v/versions= c/classifiers= origin= repository-id= l/local-repository= gradle ivy discover= project= settings= global-settings= r/releases s/snapshots installed cached opaque q/quiet V/verbose version h/help
 end
 
 function __fish_mcup_needs_command
-	# Figure out if the current invocation already has a command.
-	set -l cmd (commandline -opc)
-	set -e cmd[1]
-	argparse -s (__fish_mcup_global_optspecs) -- $cmd 2>/dev/null
-	or return
-	if set -q argv[1]
-		# Also print the command, so this can be used to figure out what it is.
-		echo $argv[1]
-		return 1
-	end
-	return 0
+    # Figure out if the current invocation already has a command.
+    set -l cmd (commandline -opc)
+    set -e cmd[1]
+    argparse -s (__fish_mcup_global_optspecs) -- $cmd 2>/dev/null
+    or return
+    if set -q argv[1]
+        # Also print the command, so this can be used to figure out what it is.
+        echo $argv[1]
+        return 1
+    end
+    return 0
 end
 
 function __fish_mcup_using_subcommand
-	set -l cmd (__fish_mcup_needs_command)
-	test -z "$cmd"
-	and return 1
-	contains -- $cmd[1] $argv
+    set -l cmd (__fish_mcup_needs_command)
+    test -z "$cmd"
+    and return 1
+    contains -- $cmd[1] $argv
 end
 
 complete -c mcup -n "__fish_mcup_needs_command" -s g -l groups -d 'Selects artifacts based on the group ID. Subgroups are included by default.' -r
 complete -c mcup -n "__fish_mcup_needs_command" -s a -l artifacts -d 'Selects artifacts based on the artifact ID. Supports globbing like in \'maven-*-plugin\'.' -r
-complete -c mcup -n "__fish_mcup_needs_command" -s v -l versions -d 'Selects artifacts based on version (ranges). Use \'<n>..\' to select the n most recent versions, \'..<n>\' to select the n oldest versions and \'<version>\' to select one specific version only.' -r
-complete -c mcup -n "__fish_mcup_needs_command" -s l -l local-repository -d 'Sets the location of the local maven repository. Respects the directory configured in \'~/.m2/settings.xml\'. Falls back to \'~/.m2/repository\', if nothing has been specified or configured.' -r
+complete -c mcup -n "__fish_mcup_needs_command" -s v -l versions -d 'Selects artifacts based on version (ranges). Use \'<n>..\' to select the n most recent versions, \'..<n>\' to select the n oldest versions and \'<version>\' to select one specific version only. Use \'latest-per:major=<n>\' or \'latest-per:minor=<n>\' to select the n most recent versions of each major or minor line. Use \'since:<yyyy-mm-dd>\', \'until:<yyyy-mm-dd>\', \'age<<n>[d|w|m|y]\' or \'age><n>[d|w|m|y]\' to select versions by the time they\'ve been downloaded or installed.' -r
+complete -c mcup -n "__fish_mcup_needs_command" -s c -l classifiers -d 'Selects files inside the versions based on a comma separated list of classifiers like in \'sources,javadoc\'. Supports globbing. Only the selected files are analyzed or removed, not the whole version.' -r
+complete -c mcup -n "__fish_mcup_needs_command" -l origin -d 'Selects versions based on their origin as recorded in \'_remote.repositories\': \'local\' for artifacts installed by \'mvn install\', \'remote\' for downloaded artifacts, or the ID of a remote repository like \'central\'. Versions without \'_remote.repositories\' are considered local.' -r
+complete -c mcup -n "__fish_mcup_needs_command" -l repository-id -d 'Selects versions downloaded from the remote repositories with the given IDs like in \'central,jboss-*\'. Supports globbing. The IDs are taken from \'_remote.repositories\' and \'maven-metadata-<repoId>.xml\'.' -r
+complete -c mcup -n "__fish_mcup_needs_command" -s l -l local-repository -d 'Sets the location of the local maven repository. Respects \'-Dmaven.repo.local\' in \'MAVEN_ARGS\' and \'MAVEN_OPTS\' and the directory configured in the user and global settings. Falls back to \'~/.m2/repository\', if nothing has been specified or configured.' -r
+complete -c mcup -n "__fish_mcup_needs_command" -l discover -d 'Runs the subcommand against all local repositories found below ROOT, e.g. the repositories of the workspaces on a CI host. Repositories are detected by the maven layout and the \'_remote.repositories\' markers. Gradle module caches are detected by \'modules-2/files-2.1\', ivy by \'.ivy2\'.' -r
+complete -c mcup -n "__fish_mcup_needs_command" -l project -d 'Uses the local repository configured with \'-Dmaven.repo.local\' in \'.mvn/maven.config\' or \'.mvn/jvm.config\' of another maven project. The project root is the first directory containing \'.mvn/\' in or above DIR. Defaults to the project in or above the current directory.' -r
+complete -c mcup -n "__fish_mcup_needs_command" -l settings -d 'Uses an alternate user settings file instead of \'~/.m2/settings.xml\' to locate the local repository' -r
+complete -c mcup -n "__fish_mcup_needs_command" -l global-settings -d 'Uses an alternate global settings file instead of \'$MAVEN_HOME/conf/settings.xml\' to locate the local repository' -r
+complete -c mcup -n "__fish_mcup_needs_command" -l gradle -d 'Uses the module cache of gradle \'$GRADLE_USER_HOME/caches/modules-2\' (defaults to \'~/.gradle/caches/modules-2\') instead of the local maven repository. Module caches are also detected when passed to \'--local-repository\'.'
+complete -c mcup -n "__fish_mcup_needs_command" -l ivy -d 'Uses the cache and local repository of ivy in \'~/.ivy2\' (used by sbt and ant) instead of the local maven repository. \'~/.ivy2\', \'~/.ivy2/cache\' and \'~/.ivy2/local\' are also detected when passed to \'--local-repository\'.'
 complete -c mcup -n "__fish_mcup_needs_command" -s r -l releases -d 'Selects released artifacts only'
 complete -c mcup -n "__fish_mcup_needs_command" -s s -l snapshots -d 'Selects snapshot artifacts only'
+complete -c mcup -n "__fish_mcup_needs_command" -l installed -d 'Selects artifacts in the \'installed\' tree of a split local repository only'
+complete -c mcup -n "__fish_mcup_needs_command" -l cached -d 'Selects artifacts in the \'cached\' tree of a split local repository only'
+complete -c mcup -n "__fish_mcup_needs_command" -l opaque -d 'Selects opaque versions only: version directories like \'latest\', \'RELEASE\', \'${revision}\' or commit hashes, which don\'t follow the maven version scheme. Opaque versions are ordered below all other versions, but version ranges don\'t count them. keep never removes them, unless this flag is given: Then keep removes the opaque versions not matched by the other filters and leaves all other versions alone.'
+complete -c mcup -n "__fish_mcup_needs_command" -s q -l quiet -d 'Prints neither progress nor the summary of keep and rm, only warnings and errors. Useful for cron jobs.'
+complete -c mcup -n "__fish_mcup_needs_command" -s V -l verbose -d 'Logs skipped directories, opaque versions and protected versions to stderr. Repeat (\'-VV\') to log the decision of the filters for each version as well. The short flag is \'-V\', since \'-v\' is \'--versions\'.'
+complete -c mcup -n "__fish_mcup_needs_command" -l version -d 'Prints version'
 complete -c mcup -n "__fish_mcup_needs_command" -s h -l help -d 'Print help'
-complete -c mcup -n "__fish_mcup_needs_command" -s V -l version -d 'Print version'
 complete -c mcup -n "__fish_mcup_needs_command" -f -a "keep" -d 'Keeps the artifacts matched by the filters and removes the rest'
 complete -c mcup -n "__fish_mcup_needs_command" -f -a "rm" -d 'Removes the artifacts matched by the filters and keeps the rest'
 complete -c mcup -n "__fish_mcup_needs_command" -f -a "du" -d 'Analyzes the size of the artifacts selected by the filters'
+complete -c mcup -n "__fish_mcup_needs_command" -f -a "versions" -d 'Lists the versions of an artifact in the order used by \'--versions\' together with their parsed components, type and size'
+complete -c mcup -n "__fish_mcup_needs_command" -f -a "version-compare" -d 'Shows how two versions are parsed and which one is greater'
 complete -c mcup -n "__fish_mcup_needs_command" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c mcup -n "__fish_mcup_using_subcommand keep" -l wait -d 'Waits up to the given timeout (like \'30s\', \'5m\' or \'1h\') while another process uses the repository. Fails immediately, if not specified.' -r
+complete -c mcup -n "__fish_mcup_using_subcommand keep" -l max-removal -d 'Refuses to remove more than the given percentage of the repository. Use 100 to disable the check.' -r
 complete -c mcup -n "__fish_mcup_using_subcommand keep" -s d -l dry-run -d 'Does not remove artifacts'
 complete -c mcup -n "__fish_mcup_using_subcommand keep" -l list -d 'Prints the full path to the artifacts that will be removed'
+complete -c mcup -n "__fish_mcup_using_subcommand keep" -s y -l yes -d 'Does not ask for confirmation before removing artifacts'
 complete -c mcup -n "__fish_mcup_using_subcommand keep" -s h -l help -d 'Print help'
-complete -c mcup -n "__fish_mcup_using_subcommand keep" -s V -l version -d 'Print version'
+complete -c mcup -n "__fish_mcup_using_subcommand rm" -l wait -d 'Waits up to the given timeout (like \'30s\', \'5m\' or \'1h\') while another process uses the repository. Fails immediately, if not specified.' -r
+complete -c mcup -n "__fish_mcup_using_subcommand rm" -l attachments -d 'Removes only the given attachments (and their checksums) of the selected versions and leaves the main artifacts untouched' -r -f -a "sources\t''
+javadoc\t''
+tests\t''"
 complete -c mcup -n "__fish_mcup_using_subcommand rm" -s d -l dry-run -d 'Does not remove artifacts'
 complete -c mcup -n "__fish_mcup_using_subcommand rm" -l list -d 'Prints the full path to the artifacts that will be removed'
+complete -c mcup -n "__fish_mcup_using_subcommand rm" -s y -l yes -d 'Does not ask for confirmation before removing artifacts'
 complete -c mcup -n "__fish_mcup_using_subcommand rm" -s h -l help -d 'Print help'
-complete -c mcup -n "__fish_mcup_using_subcommand rm" -s V -l version -d 'Print version'
-complete -c mcup -n "__fish_mcup_using_subcommand du" -s o -l output -d 'Defines whether (g)roups, (a)rtifacts and (v)ersions are included in the usage summary' -r
+complete -c mcup -n "__fish_mcup_using_subcommand du" -s o -l output -d 'Defines whether (o)rigins, (g)roups, (a)rtifacts, (v)ersions and (f)iles are included in the usage summary. Origins group the usage by the repositories the versions have been downloaded from.' -r
+complete -c mcup -n "__fish_mcup_using_subcommand du" -l apparent-size -d 'Prints apparent sizes rather than disk usage. Like \'du --apparent-size\', this is the sum of the file lengths rather than the blocks allocated on disk.'
+complete -c mcup -n "__fish_mcup_using_subcommand du" -l all -d 'Includes files which are not part of a version (maven metadata, directories which are not a version, leftovers of failed downloads, ...) as \'(unaccounted)\''
 complete -c mcup -n "__fish_mcup_using_subcommand du" -s h -l help -d 'Print help'
-complete -c mcup -n "__fish_mcup_using_subcommand du" -s V -l version -d 'Print version'
-complete -c mcup -n "__fish_mcup_using_subcommand help; and not __fish_seen_subcommand_from keep rm du help" -f -a "keep" -d 'Keeps the artifacts matched by the filters and removes the rest'
-complete -c mcup -n "__fish_mcup_using_subcommand help; and not __fish_seen_subcommand_from keep rm du help" -f -a "rm" -d 'Removes the artifacts matched by the filters and keeps the rest'
-complete -c mcup -n "__fish_mcup_using_subcommand help; and not __fish_seen_subcommand_from keep rm du help" -f -a "du" -d 'Analyzes the size of the artifacts selected by the filters'
-complete -c mcup -n "__fish_mcup_using_subcommand help; and not __fish_seen_subcommand_from keep rm du help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c mcup -n "__fish_mcup_using_subcommand versions" -s h -l help -d 'Print help'
+complete -c mcup -n "__fish_mcup_using_subcommand version-compare" -s h -l help -d 'Print help'
+complete -c mcup -n "__fish_mcup_using_subcommand help; and not __fish_seen_subcommand_from keep rm du versions version-compare help" -f -a "keep" -d 'Keeps the artifacts matched by the filters and removes the rest'
+complete -c mcup -n "__fish_mcup_using_subcommand help; and not __fish_seen_subcommand_from keep rm du versions version-compare help" -f -a "rm" -d 'Removes the artifacts matched by the filters and keeps the rest'
+complete -c mcup -n "__fish_mcup_using_subcommand help; and not __fish_seen_subcommand_from keep rm du versions version-compare help" -f -a "du" -d 'Analyzes the size of the artifacts selected by the filters'
+complete -c mcup -n "__fish_mcup_using_subcommand help; and not __fish_seen_subcommand_from keep rm du versions version-compare help" -f -a "versions" -d 'Lists the versions of an artifact in the order used by \'--versions\' together with their parsed components, type and size'
+complete -c mcup -n "__fish_mcup_using_subcommand help; and not __fish_seen_subcommand_from keep rm du versions version-compare help" -f -a "version-compare" -d 'Shows how two versions are parsed and which one is greater'
+complete -c mcup -n "__fish_mcup_using_subcommand help; and not __fish_seen_subcommand_from keep rm du versions version-compare help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
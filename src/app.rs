@@ -11,7 +11,7 @@ pub fn build_app() -> Command {
             .usage(AnsiColor::Green.on_default() | Effects::BOLD)
             .literal(AnsiColor::Blue.on_default() | Effects::BOLD)
             .placeholder(AnsiColor::Cyan.on_default()))
-        .propagate_version(true)
+        .disable_version_flag(true)
         .subcommand_required(true)
         .arg(Arg::new("groups")
             .short('g')
@@ -30,12 +30,58 @@ pub fn build_app() -> Command {
             .long("versions")
             .value_name("VERSIONS")
             .display_order(3)
-            .help("Selects artifacts based on version (ranges). Use '<n>..' to select the n most recent versions, '..<n>' to select the n oldest versions and '<version>' to select one specific version only."))
+            .help("Selects artifacts based on version (ranges). Use '<n>..' to select the n most recent versions, '..<n>' to select the n oldest versions and '<version>' to select one specific version only. Use 'latest-per:major=<n>' or 'latest-per:minor=<n>' to select the n most recent versions of each major or minor line. Use 'since:<yyyy-mm-dd>', 'until:<yyyy-mm-dd>', 'age<<n>[d|w|m|y]' or 'age><n>[d|w|m|y]' to select versions by the time they've been downloaded or installed."))
+        .arg(Arg::new("classifiers")
+            .short('c')
+            .long("classifiers")
+            .value_name("CLASSIFIERS")
+            .display_order(4)
+            .help("Selects files inside the versions based on a comma separated list of classifiers like in 'sources,javadoc'. Supports globbing. Only the selected files are analyzed or removed, not the whole version."))
+        .arg(Arg::new("origin")
+            .long("origin")
+            .value_name("ORIGIN")
+            .display_order(5)
+            .help("Selects versions based on their origin as recorded in '_remote.repositories': 'local' for artifacts installed by 'mvn install', 'remote' for downloaded artifacts, or the ID of a remote repository like 'central'. Versions without '_remote.repositories' are considered local."))
+        .arg(Arg::new("repository-id")
+            .long("repository-id")
+            .value_name("REPOSITORY_IDS")
+            .display_order(6)
+            .help("Selects versions downloaded from the remote repositories with the given IDs like in 'central,jboss-*'. Supports globbing. The IDs are taken from '_remote.repositories' and 'maven-metadata-<repoId>.xml'."))
         .arg(Arg::new("local-repository")
             .short('l')
             .long("local-repository")
             .value_name("LOCAL_REPOSITORY")
-            .help("Sets the location of the local maven repository. Respects the directory configured in '~/.m2/settings.xml'. Falls back to '~/.m2/repository', if nothing has been specified or configured."))
+            .help("Sets the location of the local maven repository. Respects '-Dmaven.repo.local' in 'MAVEN_ARGS' and 'MAVEN_OPTS' and the directory configured in the user and global settings. Falls back to '~/.m2/repository', if nothing has been specified or configured."))
+        .arg(Arg::new("gradle")
+            .long("gradle")
+            .action(ArgAction::SetTrue)
+            .conflicts_with_all(["local-repository", "project", "settings", "global-settings"])
+            .help("Uses the module cache of gradle '$GRADLE_USER_HOME/caches/modules-2' (defaults to '~/.gradle/caches/modules-2') instead of the local maven repository. Module caches are also detected when passed to '--local-repository'."))
+        .arg(Arg::new("ivy")
+            .long("ivy")
+            .action(ArgAction::SetTrue)
+            .conflicts_with_all(["local-repository", "project", "settings", "global-settings", "gradle"])
+            .help("Uses the cache and local repository of ivy in '~/.ivy2' (used by sbt and ant) instead of the local maven repository. '~/.ivy2', '~/.ivy2/cache' and '~/.ivy2/local' are also detected when passed to '--local-repository'."))
+        .arg(Arg::new("discover")
+            .long("discover")
+            .value_name("ROOT")
+            .conflicts_with_all(["local-repository", "project", "settings", "global-settings", "gradle", "ivy"])
+            .help("Runs the subcommand against all local repositories found below ROOT, e.g. the repositories of the workspaces on a CI host. Repositories are detected by the maven layout and the '_remote.repositories' markers. Gradle module caches are detected by 'modules-2/files-2.1', ivy by '.ivy2'."))
+        .arg(Arg::new("project")
+            .long("project")
+            .value_name("DIR")
+            .conflicts_with("local-repository")
+            .help("Uses the local repository configured with '-Dmaven.repo.local' in '.mvn/maven.config' or '.mvn/jvm.config' of another maven project. The project root is the first directory containing '.mvn/' in or above DIR. Defaults to the project in or above the current directory."))
+        .arg(Arg::new("settings")
+            .long("settings")
+            .value_name("FILE")
+            .conflicts_with("local-repository")
+            .help("Uses an alternate user settings file instead of '~/.m2/settings.xml' to locate the local repository"))
+        .arg(Arg::new("global-settings")
+            .long("global-settings")
+            .value_name("FILE")
+            .conflicts_with("local-repository")
+            .help("Uses an alternate global settings file instead of '$MAVEN_HOME/conf/settings.xml' to locate the local repository"))
         .arg(Arg::new("releases")
             .short('r')
             .long("releases")
@@ -48,6 +94,35 @@ pub fn build_app() -> Command {
             .action(ArgAction::SetTrue)
             .help("Selects snapshot artifacts only")
             .conflicts_with("releases"))
+        .arg(Arg::new("installed")
+            .long("installed")
+            .action(ArgAction::SetTrue)
+            .help("Selects artifacts in the 'installed' tree of a split local repository only")
+            .conflicts_with("cached"))
+        .arg(Arg::new("cached")
+            .long("cached")
+            .action(ArgAction::SetTrue)
+            .help("Selects artifacts in the 'cached' tree of a split local repository only")
+            .conflicts_with("installed"))
+        .arg(Arg::new("opaque")
+            .long("opaque")
+            .action(ArgAction::SetTrue)
+            .help("Selects opaque versions only: version directories like 'latest', 'RELEASE', '${revision}' or commit hashes, which don't follow the maven version scheme. Opaque versions are ordered below all other versions, but version ranges don't count them. keep never removes them, unless this flag is given: Then keep removes the opaque versions not matched by the other filters and leaves all other versions alone."))
+        .arg(Arg::new("quiet")
+            .short('q')
+            .long("quiet")
+            .action(ArgAction::SetTrue)
+            .conflicts_with("verbose")
+            .help("Prints neither progress nor the summary of keep and rm, only warnings and errors. Useful for cron jobs."))
+        .arg(Arg::new("verbose")
+            .short('V')
+            .long("verbose")
+            .action(ArgAction::Count)
+            .help("Logs skipped directories, opaque versions and protected versions to stderr. Repeat ('-VV') to log the decision of the filters for each version as well. The short flag is '-V', since '-v' is '--versions'."))
+        .arg(Arg::new("version")
+            .long("version")
+            .action(ArgAction::Version)
+            .help("Prints version"))
         .subcommand(Command::new("keep")
             .about("Keeps the artifacts matched by the filters and removes the rest")
             .arg(Arg::new("dry-run")
@@ -58,7 +133,22 @@ pub fn build_app() -> Command {
             .arg(Arg::new("list")
                 .long("list")
                 .action(ArgAction::SetTrue)
-                .help("Prints the full path to the artifacts that will be removed")))
+                .help("Prints the full path to the artifacts that will be removed"))
+            .arg(Arg::new("yes")
+                .short('y')
+                .long("yes")
+                .action(ArgAction::SetTrue)
+                .help("Does not ask for confirmation before removing artifacts"))
+            .arg(Arg::new("wait")
+                .long("wait")
+                .value_name("TIMEOUT")
+                .help("Waits up to the given timeout (like '30s', '5m' or '1h') while another process uses the repository. Fails immediately, if not specified."))
+            .arg(Arg::new("max-removal")
+                .long("max-removal")
+                .value_name("PERCENT")
+                .value_parser(clap::value_parser!(u8).range(1..=100))
+                .default_value("50")
+                .help("Refuses to remove more than the given percentage of the repository. Use 100 to disable the check.")))
         .subcommand(Command::new("rm")
             .about("Removes the artifacts matched by the filters and keeps the rest")
             .arg(Arg::new("dry-run")
@@ -69,7 +159,22 @@ pub fn build_app() -> Command {
             .arg(Arg::new("list")
                 .long("list")
                 .action(ArgAction::SetTrue)
-                .help("Prints the full path to the artifacts that will be removed")))
+                .help("Prints the full path to the artifacts that will be removed"))
+            .arg(Arg::new("yes")
+                .short('y')
+                .long("yes")
+                .action(ArgAction::SetTrue)
+                .help("Does not ask for confirmation before removing artifacts"))
+            .arg(Arg::new("wait")
+                .long("wait")
+                .value_name("TIMEOUT")
+                .help("Waits up to the given timeout (like '30s', '5m' or '1h') while another process uses the repository. Fails immediately, if not specified."))
+            .arg(Arg::new("attachments")
+                .long("attachments")
+                .value_name("ATTACHMENTS")
+                .value_delimiter(',')
+                .value_parser(["sources", "javadoc", "tests"])
+                .help("Removes only the given attachments (and their checksums) of the selected versions and leaves the main artifacts untouched")))
         .subcommand(Command::new("du")
             .about("Analyzes the size of the artifacts selected by the filters")
             .arg(Arg::new("output")
@@ -77,5 +182,29 @@ pub fn build_app() -> Command {
                 .long("output")
                 .value_name("OUTPUT")
                 .default_value("ga")
-                .help("Defines whether (g)roups, (a)rtifacts and (v)ersions are included in the usage summary")))
+                .help("Defines whether (o)rigins, (g)roups, (a)rtifacts, (v)ersions and (f)iles are included in the usage summary. Origins group the usage by the repositories the versions have been downloaded from."))
+            .arg(Arg::new("apparent-size")
+                .long("apparent-size")
+                .action(ArgAction::SetTrue)
+                .help("Prints apparent sizes rather than disk usage. Like 'du --apparent-size', this is the sum of the file lengths rather than the blocks allocated on disk."))
+            .arg(Arg::new("all")
+                .long("all")
+                .action(ArgAction::SetTrue)
+                .help("Includes files which are not part of a version (maven metadata, directories which are not a version, leftovers of failed downloads, ...) as '(unaccounted)'")))
+        .subcommand(Command::new("versions")
+            .about("Lists the versions of an artifact in the order used by '--versions' together with their parsed components, type and size")
+            .arg(Arg::new("coordinates")
+                .value_name("GROUP_ID:ARTIFACT_ID")
+                .required(true)
+                .help("The artifact like 'org.foo:bar'")))
+        .subcommand(Command::new("version-compare")
+            .about("Shows how two versions are parsed and which one is greater")
+            .arg(Arg::new("a")
+                .value_name("A")
+                .required(true)
+                .help("The first version"))
+            .arg(Arg::new("b")
+                .value_name("B")
+                .required(true)
+                .help("The second version")))
 }
@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 use std::path::{Path, PathBuf};
 
-use clap::ArgMatches;
+use anyhow::{bail, Result};
 use glob::Pattern;
 
 use crate::version::Version;
@@ -13,8 +13,11 @@ use crate::version::Version;
 pub struct Artifact {
     pub id: String,
     pub path: PathBuf,
-    pub versions: BTreeMap<Version, Version>,
+    // The same version can live in several roots of a split repository, so
+    // the path of the version breaks the tie.
+    pub versions: BTreeMap<(Version, PathBuf), Version>,
     pub bytes: u64,
+    pub apparent_bytes: u64,
 }
 
 impl Artifact {
@@ -24,8 +27,15 @@ impl Artifact {
             path: path.to_path_buf(),
             versions: BTreeMap::new(),
             bytes: 0,
+            apparent_bytes: 0,
         }
     }
+
+    pub fn add_version(&mut self, version: Version) {
+        self.versions
+            .entry((version.clone(), version.path.clone()))
+            .or_insert(version);
+    }
 }
 
 impl PartialEq for Artifact {
@@ -44,16 +54,17 @@ impl Display for Artifact {
 
 // ------------------------------------------------------ artifact filter
 
+#[derive(Debug, Clone)]
 pub struct ArtifactFilter {
     pub artifacts: Pattern,
 }
 
 impl ArtifactFilter {
-    pub fn from(args: &ArgMatches) -> Option<ArtifactFilter> {
-        args.get_one::<Pattern>("artifacts")
-            .map(|artifact| ArtifactFilter {
-                artifacts: artifact.clone(),
-            })
+    pub fn parse(artifacts: &str) -> Result<ArtifactFilter> {
+        match Pattern::new(artifacts) {
+            Ok(artifacts) => Ok(ArtifactFilter { artifacts }),
+            Err(e) => bail!("Illegal artifact pattern '{}': {}", artifacts, e.msg),
+        }
     }
 
     pub fn match_artifact_id(&self, artifact_id: &str) -> bool {
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs::remove_dir;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Result;
+use walkdir::WalkDir;
+
+use crate::file::ArtifactFile;
+use crate::gradle::{is_module_cache, Gradle};
+use crate::group::GroupFilter;
+use crate::ivy::{is_ivy, Ivy};
+use crate::layout::Layout;
+use crate::repo::Gav;
+use crate::usage::DiskUsage;
+use crate::version::Version;
+
+// ------------------------------------------------------ backend
+
+// The storage of a repository: where the versions are, how big they are and
+// how to remove them. Repository implements du, keep and rm against this trait.
+pub trait Backend: Debug + Send + Sync {
+    // Walks the repository and passes each version to 'visit'. The walk predicate
+    // gets the paths of groups, artifacts and versions relative to the roots in
+    // the maven layout ('org/foo/bar/1.0') and decides whether to descend.
+    fn collect(&self, walk_predicate: &mut dyn FnMut(&Path) -> bool, visit: &mut dyn FnMut(Gav));
+
+    // Measures the size of a version and collects its artifact files
+    fn measure(&self, version: &mut Version, artifact_id: &str, disk_usage: &mut DiskUsage);
+
+    // The paths removed together with a version
+    fn version_paths(&self, version: &Version) -> Vec<PathBuf> {
+        vec![version.path.clone()]
+    }
+
+    fn remove(&self, version: &Version) -> Result<()>;
+
+    // Removes the directories left empty after removing a version or its files
+    fn prune(&self, version: &Version);
+
+    // Whether the directory belongs to a version (du --all)
+    fn version_dir(&self, path: &Path) -> bool;
+
+    // Whether the file belongs to a version outside a version directory (du --all)
+    fn version_file(&self, _path: &Path) -> bool {
+        false
+    }
+
+    // The directories of a group (du --all)
+    fn group_paths(&self, group_id: &str) -> Vec<PathBuf>;
+
+    // The directories which contain the groups selected by the filter (du --all)
+    fn group_scopes(&self, group_filter: &GroupFilter) -> Vec<PathBuf>;
+
+    // Whether the repository is split into 'installed/' and 'cached/'
+    fn split(&self) -> bool {
+        false
+    }
+}
+
+// Detects the backend of the repository at 'path'
+pub fn detect(path: &Path, properties: &HashMap<String, String>) -> Arc<dyn Backend> {
+    if is_module_cache(path) {
+        Arc::new(Gradle::new(path))
+    } else if is_ivy(path) {
+        Arc::new(Ivy::new(path))
+    } else {
+        Arc::new(Layout::detect(path, properties))
+    }
+}
+
+// ------------------------------------------------------ helpers
+
+// Walks 'root' and calls 'visit' for each version directory. Directories
+// for which 'walk_predicate' returns false are skipped.
+pub fn walk_versions<P, V>(
+    root: &Path,
+    mut walk_predicate: P,
+    version_dir: V,
+    visit: &mut dyn FnMut(&Path, bool),
+) where
+    P: FnMut(&Path) -> bool,
+    V: Fn(&Path) -> Option<bool>,
+{
+    for dir_entry in WalkDir::new(root)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|dir_entry| {
+            dir_entry
+                .path()
+                .strip_prefix(root)
+                .map_or(true, &mut walk_predicate)
+        })
+        .filter_map(|e| e.ok())
+    {
+        if !dir_entry.file_type().is_dir() {
+            continue;
+        }
+        if let Some(pom) = version_dir(dir_entry.path()) {
+            visit(dir_entry.path(), pom);
+        }
+    }
+}
+
+// Measures the version directory and 'extra' paths. Files at 'file_depth'
+// below the version directory are artifact files. Everything else counts
+// for the version only.
+pub fn measure_version(
+    version: &mut Version,
+    artifact_id: &str,
+    file_depth: usize,
+    extra: &[PathBuf],
+    disk_usage: &mut DiskUsage,
+) {
+    let version_name = version
+        .path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("")
+        .to_string();
+    let mut version_bytes: u64 = 0;
+    let mut version_apparent_bytes: u64 = 0;
+    let mut files: Vec<ArtifactFile> = Vec::new();
+    for dir_entry in WalkDir::new(version.path.as_path())
+        .into_iter()
+        .chain(extra.iter().flat_map(WalkDir::new))
+        .filter_map(|e| e.ok())
+    {
+        if !dir_entry.file_type().is_symlink() {
+            if let Ok(meta) = dir_entry.metadata() {
+                let (bytes, apparent_bytes) = disk_usage.file(&meta);
+                version_bytes += bytes;
+                version_apparent_bytes += apparent_bytes;
+                if dir_entry.depth() == file_depth
+                    && dir_entry.file_type().is_file()
+                    && dir_entry.path().starts_with(version.path.as_path())
+                {
+                    let mut file = ArtifactFile::new(
+                        dir_entry.path(),
+                        artifact_id,
+                        version_name.as_str(),
+                        version.snapshot,
+                    );
+                    file.bytes = bytes;
+                    file.apparent_bytes = apparent_bytes;
+                    files.push(file);
+                }
+            }
+        }
+    }
+    version.bytes = version_bytes;
+    version.apparent_bytes = version_apparent_bytes;
+    version.files = files;
+}
+
+// Returns the group directories inside 'dirs' selected by the group filter.
+// For layouts which use the group ID as directory name like 'org.foo'.
+pub fn flat_group_scopes<'a>(
+    dirs: impl Iterator<Item = &'a PathBuf>,
+    group_filter: &GroupFilter,
+) -> Vec<PathBuf> {
+    dirs.filter_map(|path| path.read_dir().ok())
+        .flat_map(|entries| entries.flatten())
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| group_filter.match_group_id(name))
+        })
+        .map(|entry| entry.path())
+        .collect()
+}
+
+// Removes 'path' and the artifact and group directories above, if empty
+pub fn prune_version_dir(path: &Path) {
+    let _ = remove_dir(path);
+    if let Some(artifact_path) = path.parent() {
+        let _ = remove_dir(artifact_path);
+        if let Some(group_path) = artifact_path.parent() {
+            let _ = remove_dir(group_path);
+        }
+    }
+}
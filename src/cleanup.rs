@@ -0,0 +1,228 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Result};
+use console::style;
+
+use crate::command::Command;
+use crate::command::Command::{Keep, Remove};
+use crate::event::Observer;
+use crate::filter::Filter;
+use crate::lock::RepositoryLock;
+use crate::protect::Protection;
+use crate::repo::Repository;
+
+// ------------------------------------------------------ cleanup
+
+// Runs du, keep or rm against one repository:
+//
+//   let report = Cleanup::new(Repository::open(path), Command::Remove(false, false))
+//       .filter(Filter::builder().groups("org.foo").snapshots().build()?)
+//       .run()?;
+//
+// run() selects and removes in one go. Use select() and Selection::remove()
+// to inspect or confirm the selection before anything is removed.
+pub struct Cleanup {
+    repository: Repository,
+    command: Command,
+    filter: Filter,
+    wait: Duration,
+}
+
+impl Cleanup {
+    pub fn new(repository: Repository, command: Command) -> Cleanup {
+        Cleanup {
+            repository,
+            command,
+            filter: Filter::default(),
+            wait: Duration::ZERO,
+        }
+    }
+
+    pub fn filter(mut self, filter: Filter) -> Cleanup {
+        self.filter = filter;
+        self
+    }
+
+    // How long to wait for another instance of mcup or a running build
+    pub fn wait(mut self, wait: Duration) -> Cleanup {
+        self.wait = wait;
+        self
+    }
+
+    // Gets notified while the repository is scanned and cleaned up
+    pub fn observer(mut self, observer: Arc<dyn Observer>) -> Cleanup {
+        self.repository.observer = observer;
+        self
+    }
+
+    // Collects and measures the selected versions. keep and rm lock the
+    // repository until the selection has been removed or dropped.
+    pub fn select(mut self) -> Result<Selection> {
+        if let Keep(_, _) | Remove(_, _) = self.command {
+            if self.filter.is_empty() {
+                bail!("keep and rm require a filter")
+            }
+        }
+        if let Some(prefix) = self.filter.prefix {
+            if !self.repository.backend.split() {
+                bail!(
+                    "The argument {} requires a split local repository, but '{}' is not split",
+                    style(format!("'--{}'", prefix)).yellow(),
+                    self.repository.path.display()
+                )
+            }
+        }
+        if self.filter.protection.is_none() {
+            self.filter.protection = Protection::load(self.repository.path.as_path())?;
+        }
+        let lock = match self.command {
+            Keep(false, _) | Remove(false, _) => Some(RepositoryLock::acquire(
+                self.repository.path.as_path(),
+                self.wait,
+            )?),
+            _ => None,
+        };
+        let duration = self.repository.select(&self.command, &self.filter);
+        Ok(Selection {
+            repository: self.repository,
+            command: self.command,
+            filter: self.filter,
+            wait: self.wait,
+            lock,
+            duration,
+        })
+    }
+
+    pub fn run(self) -> Result<Report> {
+        self.select()?.remove()
+    }
+}
+
+// ------------------------------------------------------ selection
+
+// The repository contains the selected versions: the ones to analyze (du)
+// or the ones to remove (keep and rm).
+pub struct Selection {
+    pub repository: Repository,
+    pub command: Command,
+    pub filter: Filter,
+    pub duration: Duration,
+    wait: Duration,
+    lock: Option<RepositoryLock>,
+}
+
+impl Selection {
+    // Removes the selected versions or files (keep and rm only). Waits for
+    // running builds first and releases the lock afterwards.
+    pub fn remove(self) -> Result<Report> {
+        if let Some(lock) = &self.lock {
+            lock.wait_for_builds(&self.repository, self.wait)?;
+        }
+        let now = Instant::now();
+        let paths = self.repository.remove(&self.command, &self.filter);
+        Ok(Report {
+            repository: self.repository,
+            command: self.command,
+            filter: self.filter,
+            paths,
+            duration: self.duration + now.elapsed(),
+        })
+    }
+}
+
+// ------------------------------------------------------ report
+
+pub struct Report {
+    pub repository: Repository,
+    pub command: Command,
+    pub filter: Filter,
+    // the removed paths or the paths which would be removed by a dry run
+    pub paths: Vec<PathBuf>,
+    pub duration: Duration,
+}
+
+// ------------------------------------------------------ cleanup tests
+
+#[cfg(test)]
+mod cleanup_tests {
+    use std::fs::remove_dir_all;
+    use std::sync::{Arc, Mutex};
+
+    use crate::cleanup::Cleanup;
+    use crate::command::Command::Remove;
+    use crate::event::{Event, Observer};
+    use crate::filter::Filter;
+    use crate::repo::Repository;
+    use crate::temp::TempDir;
+
+    #[derive(Debug, Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl Observer for Recorder {
+        fn notify(&self, event: &Event) {
+            let event = match event {
+                Event::ScanStart => "scan start".to_string(),
+                Event::Scan(gav) => format!("scan {gav}"),
+                Event::ScanEnd => "scan end".to_string(),
+                Event::Select { version, .. } => format!("select {version}"),
+                Event::RemovalStart(count) => format!("removal start {count}"),
+                Event::Remove(paths) => format!("remove {}", paths.len()),
+                Event::RemovalEnd => "removal end".to_string(),
+                Event::Error(path, _) => format!("error {}", path.display()),
+            };
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
+    #[test]
+    fn events() {
+        let repo = TempDir::new("cleanup-events");
+        for version in ["1.0", "2.0", "3.0"] {
+            repo.file(format!("org/foo/bar/{version}/bar-{version}.pom"), "");
+        }
+        let recorder = Arc::new(Recorder::default());
+        let selection = Cleanup::new(Repository::open(repo.path.clone()), Remove(false, false))
+            .filter(Filter::builder().versions("..2").build().unwrap())
+            .observer(recorder.clone())
+            .select()
+            .unwrap();
+        assert_eq!(2, selection.repository.versions);
+
+        remove_dir_all(repo.join("org/foo/bar/1.0")).unwrap();
+        let report = selection.remove().unwrap();
+        assert_eq!(vec![repo.join("org/foo/bar/2.0")], report.paths);
+        assert!(!repo.join("org/foo/bar/2.0").exists());
+        assert!(repo.join("org/foo/bar/3.0").exists());
+
+        let mut events = recorder.0.lock().unwrap().clone();
+        events[1..4].sort();
+        assert_eq!(
+            vec![
+                "scan start".to_string(),
+                "scan org.foo:bar:1.0".to_string(),
+                "scan org.foo:bar:2.0".to_string(),
+                "scan org.foo:bar:3.0".to_string(),
+                "scan end".to_string(),
+                "select 1.0".to_string(),
+                "select 2.0".to_string(),
+                "removal start 2".to_string(),
+                format!("error {}", repo.join("org/foo/bar/1.0").display()),
+                "remove 1".to_string(),
+                "removal end".to_string(),
+            ],
+            events
+        );
+    }
+
+    #[test]
+    fn filter_required() {
+        let repo = TempDir::new("cleanup-filter-required");
+        assert!(
+            Cleanup::new(Repository::open(repo.path.clone()), Remove(true, false))
+                .select()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use clap::ArgMatches;
+use console::style;
+use mcup::command::Command::{Du, Keep, Remove};
+use mcup::command::{Command, DuOptions};
+use mcup::filter::Filter;
+use mcup::repo::Location;
+use mcup::settings::MavenLocation;
+
+// ------------------------------------------------------ arguments
+
+pub fn command(args: &ArgMatches) -> Command {
+    if args.subcommand_matches("keep").is_some() {
+        let sub_args = args.subcommand_matches("keep").unwrap();
+        Keep(sub_args.get_flag("dry-run"), sub_args.get_flag("list"))
+    } else if args.subcommand_matches("rm").is_some() {
+        let sub_args = args.subcommand_matches("rm").unwrap();
+        Remove(sub_args.get_flag("dry-run"), sub_args.get_flag("list"))
+    } else if args.subcommand_matches("du").is_some() {
+        let sub_args = args.subcommand_matches("du").unwrap();
+        let output = sub_args.get_one::<String>("output");
+        let (origins, groups, artifacts, versions, files) = if let Some(output) = output {
+            (
+                output.contains('o'),
+                output.contains('g'),
+                output.contains('a'),
+                output.contains('v'),
+                output.contains('f'),
+            )
+        } else {
+            (false, false, false, false, false)
+        };
+        Du(DuOptions {
+            origins,
+            groups,
+            artifacts,
+            versions,
+            files,
+            apparent_size: sub_args.get_flag("apparent-size"),
+            all: sub_args.get_flag("all"),
+        })
+    } else {
+        // Should not happen, since we use
+        // AppSettings::SubcommandRequired
+        panic!("No subcommand!")
+    }
+}
+
+pub fn location(args: &ArgMatches) -> Location {
+    if args.get_flag("gradle") {
+        Location::Gradle
+    } else if args.get_flag("ivy") {
+        Location::Ivy
+    } else {
+        let path = |id: &str| args.get_one::<String>(id).map(PathBuf::from);
+        Location::Maven(MavenLocation {
+            local_repository: path("local-repository"),
+            project: path("project"),
+            settings: path("settings"),
+            global_settings: path("global-settings"),
+        })
+    }
+}
+
+// The protection is loaded by Cleanup::select()
+pub fn filter(args: &ArgMatches) -> Result<Filter> {
+    let mut builder = Filter::builder();
+    if let Some(groups) = args.get_one::<String>("groups") {
+        builder = builder.groups(groups);
+    }
+    if let Some(artifacts) = args.get_one::<String>("artifacts") {
+        builder = builder.artifacts(artifacts);
+    }
+    if let Some(versions) = args.get_one::<String>("versions") {
+        builder = builder.versions(versions);
+    }
+    if args.get_flag("releases") {
+        builder = builder.releases();
+    } else if args.get_flag("snapshots") {
+        builder = builder.snapshots();
+    }
+    if let Some(classifiers) = args.get_one::<String>("classifiers") {
+        builder = builder.classifiers(classifiers);
+    }
+    if let Some(attachments) = args
+        .subcommand_matches("rm")
+        .and_then(|sub_args| sub_args.get_many::<String>("attachments"))
+    {
+        for attachment in attachments {
+            builder = builder.attachment(attachment);
+        }
+    }
+    if let Some(origin) = args.get_one::<String>("origin") {
+        builder = builder.origin(origin);
+    }
+    if let Some(repository_ids) = args.get_one::<String>("repository-id") {
+        builder = builder.repository_ids(repository_ids);
+    }
+    if args.get_flag("installed") {
+        builder = builder.installed();
+    } else if args.get_flag("cached") {
+        builder = builder.cached();
+    }
+    if args.get_flag("opaque") {
+        builder = builder.opaque();
+    }
+    builder.build()
+}
+
+pub fn wait_timeout(args: &ArgMatches) -> Duration {
+    args.subcommand()
+        .and_then(|(_, sub_args)| sub_args.try_get_one::<Duration>("wait").ok().flatten())
+        .copied()
+        .unwrap_or(Duration::ZERO)
+}
+
+// ------------------------------------------------------ validation
+
+pub fn validate_command(args: &ArgMatches) -> Result<()> {
+    let attachments = args
+        .subcommand_matches("rm")
+        .is_some_and(|sub_args| sub_args.contains_id("attachments"));
+    if (args.subcommand_matches("keep").is_some() || args.subcommand_matches("rm").is_some())
+        && !attachments
+        && !args.contains_id("groups")
+        && !args.contains_id("artifacts")
+        && !args.contains_id("versions")
+        && !args.contains_id("classifiers")
+        && !args.contains_id("origin")
+        && !args.contains_id("repository-id")
+        && !args.get_flag("snapshots")
+        && !args.get_flag("releases")
+        && !args.get_flag("installed")
+        && !args.get_flag("cached")
+        && !args.get_flag("opaque")
+    {
+        bail!(
+            r#"Subcommand {} requires a filter, but one was not provided
+
+USAGE:
+    mcup [FLAGS] [OPTIONS] <SUBCOMMAND>
+
+For more information try {}"#,
+            style(format!("'{}'", args.subcommand_name().unwrap())).yellow(),
+            style("--help").green()
+        )
+    }
+    Ok(())
+}
+
+// ------------------------------------------------------ value parsers
+
+// Parses timeouts like '30', '30s', '5m' or '1h'
+pub fn parse_timeout(timeout: &str) -> Result<Duration, String> {
+    let (value, unit) = match timeout.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => timeout.split_at(index),
+        None => (timeout, "s"),
+    };
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        _ => return Err(format!("Illegal timeout: {timeout}. Use <n>[s|m|h].")),
+    };
+    value
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(seconds))
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("Illegal timeout: {timeout}. Use <n>[s|m|h]."))
+}
+
+// ------------------------------------------------------ cli tests
+
+#[cfg(test)]
+mod cli_tests {
+    use std::time::Duration;
+
+    use crate::cli::parse_timeout;
+
+    #[test]
+    fn timeout() {
+        assert_eq!(Duration::from_secs(30), parse_timeout("30").unwrap());
+        assert_eq!(Duration::from_secs(30), parse_timeout("30s").unwrap());
+        assert_eq!(Duration::from_secs(300), parse_timeout("5m").unwrap());
+        assert_eq!(Duration::from_secs(3600), parse_timeout("1h").unwrap());
+    }
+
+    #[test]
+    fn invalid_timeout() {
+        assert!(parse_timeout("").is_err());
+        assert!(parse_timeout("s").is_err());
+        assert!(parse_timeout("5d").is_err());
+        assert!(parse_timeout("99999999999999999h").is_err());
+    }
+}
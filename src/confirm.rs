@@ -0,0 +1,93 @@
+use std::io::{stdout, IsTerminal, Write};
+
+use anyhow::{bail, Result};
+use clap::ArgMatches;
+use console::{style, Term};
+use indicatif::DecimalBytes;
+use mcup::command::Command;
+use mcup::command::Command::{Keep, Remove};
+use mcup::filter::Filter;
+use mcup::output::{affected, protected};
+use mcup::repo::Repository;
+
+// ------------------------------------------------------ confirmation
+
+pub struct Confirmation {
+    pub yes: bool,
+    pub max_removal: Option<u8>,
+}
+
+impl Confirmation {
+    pub fn from(args: &ArgMatches) -> Confirmation {
+        match args.subcommand() {
+            Some(("keep", sub_args)) => Confirmation {
+                yes: sub_args.get_flag("yes"),
+                max_removal: sub_args.get_one::<u8>("max-removal").copied(),
+            },
+            Some(("rm", sub_args)) => Confirmation {
+                yes: sub_args.get_flag("yes"),
+                max_removal: None,
+            },
+            _ => Confirmation {
+                yes: true,
+                max_removal: None,
+            },
+        }
+    }
+
+    // Returns whether the selected artifacts should be removed. Asks the user
+    // if stdout is a terminal, and fails if keep would remove more than the
+    // allowed percentage of the repository.
+    pub fn confirm(
+        &self,
+        repository: &Repository,
+        command: &Command,
+        filter: &Filter,
+    ) -> Result<bool> {
+        match command {
+            Keep(false, _) | Remove(false, _) if !repository.is_empty() => {}
+            _ => return Ok(true),
+        }
+
+        if let (Keep(_, _), Some(max_removal)) = (command, self.max_removal) {
+            let percent = repository
+                .bytes
+                .saturating_mul(100)
+                .checked_div(repository.disk_usage())
+                .unwrap_or(0);
+            if percent > max_removal as u64 {
+                bail!(
+                    "Refusing to remove {} ({}% of the repository), which exceeds {} {}%",
+                    DecimalBytes(repository.bytes),
+                    percent,
+                    style("--max-removal").yellow(),
+                    max_removal
+                )
+            }
+        }
+
+        if self.yes || !stdout().is_terminal() {
+            return Ok(true);
+        }
+
+        let out = &mut stdout();
+        writeln!(out)?;
+        writeln!(out, "The operation will remove")?;
+        writeln!(out)?;
+        affected(out, repository, filter.classifier_filter.is_some())?;
+        writeln!(out)?;
+        protected(out, repository)?;
+        write!(
+            out,
+            "and release {}. Do you want to continue? [y/N] ",
+            style(DecimalBytes(repository.bytes)).green()
+        )?;
+        out.flush()?;
+        let answer = Term::stdout().read_line()?;
+        let confirmed = matches!(answer.trim().to_lowercase().as_str(), "y" | "yes");
+        if !confirmed {
+            writeln!(out, "No artifacts have been removed.")?;
+        }
+        Ok(confirmed)
+    }
+}
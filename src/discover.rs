@@ -0,0 +1,110 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Result};
+use walkdir::WalkDir;
+
+use crate::gradle::is_module_cache;
+use crate::ivy::is_ivy;
+use crate::layout::{repository_root, DEFAULT_LOCAL_PREFIX, DEFAULT_REMOTE_PREFIX};
+use crate::origin::REMOTE_REPOSITORIES;
+
+// ------------------------------------------------------ discover
+
+// Finds the local repositories below 'root'. A directory is a local repository if
+//   - it's the default location '.m2/repository' or
+//   - it contains a version directory with a '_remote.repositories' marker. The
+//     root of the repository is derived from the group ID in the pom of that version
+//     and the 'installed/' and 'cached/' trees of split repositories or
+//   - it's the module cache of gradle containing 'files-2.1/' or
+//   - it's the home directory of ivy '.ivy2'.
+// Repositories inside other repositories are not reported.
+pub fn discover(root: &Path) -> Result<Vec<PathBuf>> {
+    if !root.is_dir() {
+        bail!("Directory does not exist: '{}'", root.display())
+    }
+
+    let mut repositories: BTreeSet<PathBuf> = BTreeSet::new();
+    let mut it = WalkDir::new(root).into_iter();
+    while let Some(entry) = it.next() {
+        let Ok(entry) = entry else { continue };
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+        let path = entry.path();
+        if repositories
+            .iter()
+            .any(|repository| path.starts_with(repository))
+        {
+            it.skip_current_dir();
+            continue;
+        }
+        if path.ends_with(".m2/repository") || is_module_cache(path) || is_ivy(path) {
+            repositories.insert(path.to_path_buf());
+            it.skip_current_dir();
+        } else if path.join(REMOTE_REPOSITORIES).is_file() {
+            if let Some(repository) = repository_root(path).map(split_repository) {
+                if repository.starts_with(root) {
+                    repositories.insert(repository);
+                }
+            }
+            it.skip_current_dir();
+        }
+    }
+    Ok(repositories.into_iter().collect())
+}
+
+// Returns the repository itself if 'root' is inside the 'installed/' or
+// 'cached/' tree of a split repository
+fn split_repository(root: PathBuf) -> PathBuf {
+    root.ancestors()
+        .find(|path| {
+            path.file_name()
+                .is_some_and(|name| name == DEFAULT_LOCAL_PREFIX || name == DEFAULT_REMOTE_PREFIX)
+        })
+        .and_then(Path::parent)
+        .map(Path::to_path_buf)
+        .unwrap_or(root)
+}
+
+// ------------------------------------------------------ discover tests
+
+#[cfg(test)]
+mod discover_tests {
+    use crate::discover::discover;
+    use crate::temp::TempDir;
+
+    #[test]
+    fn repositories() {
+        let temp = TempDir::new("discover");
+        let root = temp.path.clone();
+        let version = |repository: &str, group: &str, pom_group: &str| {
+            let path = root.join(repository).join(group).join("bar/1.0");
+            temp.file(path.join("_remote.repositories"), "bar-1.0.pom>central=\n");
+            temp.file(
+                path.join("bar-1.0.pom"),
+                format!("<project><groupId>{}</groupId></project>", pom_group),
+            );
+        };
+        version("ws1/.m2/repository", "org/foo", "org.foo");
+        version("ws2/target/repo", "org/foo", "org.foo");
+        version("ws2/target/repo", "com/acme", "com.acme");
+        // group ID doesn't match the layout
+        version("ws3/repo", "org/foo", "com.acme");
+        version("ws4/split/cached/central", "org/foo", "org.foo");
+        temp.dir("ws5/.gradle/caches/modules-2/files-2.1");
+        temp.dir("ws6/.ivy2/cache");
+
+        let repositories = discover(&root).unwrap();
+        assert_eq!(
+            vec![
+                root.join("ws1/.m2/repository"),
+                root.join("ws2/target/repo"),
+                root.join("ws4/split"),
+                root.join("ws5/.gradle/caches/modules-2"),
+                root.join("ws6/.ivy2"),
+            ],
+            repositories
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+
+use anyhow::Error;
+
+use crate::repo::Gav;
+use crate::version::Version;
+
+// ------------------------------------------------------ events
+
+// What happens while a repository is scanned and cleaned up. The repository
+// doesn't print anything, observers turn the events into progress bars,
+// listings, reports or nothing at all.
+#[derive(Debug)]
+pub enum Event<'a> {
+    ScanStart,
+    // the walk found a version (before the filters apply)
+    Scan(&'a Gav),
+    ScanEnd,
+    // a version which is part of the selection
+    Select {
+        group_id: &'a str,
+        artifact_id: &'a str,
+        version: &'a Version,
+    },
+    // the removal of 'count' versions or files starts
+    RemovalStart(usize),
+    // the paths of a version or file have been removed (or would be removed by a dry run)
+    Remove(&'a [PathBuf]),
+    RemovalEnd,
+    // a version or file could not be removed
+    Error(&'a Path, &'a Error),
+}
+
+pub trait Observer: Debug + Send + Sync {
+    fn notify(&self, event: &Event);
+}
+
+// Ignores all events
+#[derive(Debug, Default)]
+pub struct Silent;
+
+impl Observer for Silent {
+    fn notify(&self, _event: &Event) {}
+}
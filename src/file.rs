@@ -0,0 +1,185 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use glob::Pattern;
+
+use crate::repo::artifact_file_suffix;
+
+// ------------------------------------------------------ file
+
+// A file inside a version directory. Files which follow the maven layout
+// 'artifactId-version[-classifier].ext' are artifact files and have an
+// extension and an optional classifier. Checksums and signatures share the
+// classifier of the file they belong to: 'foo-1.0-sources.jar.sha1' has
+// classifier 'sources' and extension 'jar.sha1'.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ArtifactFile {
+    pub name: String,
+    pub path: PathBuf,
+    pub artifact: bool,
+    pub classifier: Option<String>,
+    pub extension: Option<String>,
+    pub bytes: u64,
+    pub apparent_bytes: u64,
+}
+
+impl ArtifactFile {
+    pub fn new(path: &Path, artifact_id: &str, version: &str, snapshot: bool) -> ArtifactFile {
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("");
+        ArtifactFile::with_suffix(
+            path,
+            artifact_file_suffix(artifact_id, version, snapshot, name),
+        )
+    }
+
+    // Creates a file using the part of the file name which follows the
+    // artifact ID and version: '[-classifier].ext'. No suffix, no artifact file.
+    pub fn with_suffix(path: &Path, suffix: Option<&str>) -> ArtifactFile {
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("")
+            .to_string();
+        let (artifact, classifier, extension) = match suffix {
+            Some(suffix) => {
+                let (classifier, extension) = match suffix.strip_prefix('-') {
+                    Some(classified) => match classified.split_once('.') {
+                        Some((classifier, extension)) => (Some(classifier), Some(extension)),
+                        None => (Some(classified), None),
+                    },
+                    None => (None, suffix.strip_prefix('.')),
+                };
+                (
+                    true,
+                    classifier.map(String::from),
+                    extension.map(String::from),
+                )
+            }
+            None => (false, None, None),
+        };
+        ArtifactFile {
+            name,
+            path: path.to_path_buf(),
+            artifact,
+            classifier,
+            extension,
+            bytes: 0,
+            apparent_bytes: 0,
+        }
+    }
+}
+
+impl Display for ArtifactFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+// ------------------------------------------------------ classifier filter
+
+#[derive(Debug, Clone)]
+pub struct ClassifierFilter {
+    pub classifiers: Vec<Pattern>,
+}
+
+impl ClassifierFilter {
+    // Parses a comma separated list of patterns like 'sources,*javadoc'
+    pub fn parse(classifiers: &str) -> Result<ClassifierFilter> {
+        let classifiers = classifiers
+            .split(',')
+            .map(|classifier| match Pattern::new(classifier.trim()) {
+                Ok(p) => Ok(p),
+                Err(e) => Err(anyhow!(
+                    "Illegal classifier pattern '{}': {}",
+                    classifier,
+                    e.msg
+                )),
+            })
+            .collect::<Result<Vec<Pattern>>>()?;
+        Ok(ClassifierFilter { classifiers })
+    }
+
+    // Selects the classifiers of attachments like 'sources', 'javadoc' or 'tests' (rm --attachments)
+    pub fn attachments<'a>(attachments: impl IntoIterator<Item = &'a str>) -> ClassifierFilter {
+        ClassifierFilter {
+            classifiers: attachments
+                .into_iter()
+                .flat_map(attachment_classifiers)
+                .filter_map(|classifier| Pattern::new(classifier).ok())
+                .collect(),
+        }
+    }
+
+    pub fn match_classifier(&self, file: &ArtifactFile) -> bool {
+        match &file.classifier {
+            Some(classifier) => self
+                .classifiers
+                .iter()
+                .any(|pattern| pattern.matches(classifier.as_str())),
+            None => false,
+        }
+    }
+}
+
+// Classifiers of the attachments which are deployed next to the main artifact
+fn attachment_classifiers(attachment: &str) -> &'static [&'static str] {
+    match attachment {
+        "sources" => &["sources", "test-sources"],
+        "javadoc" => &["javadoc", "test-javadoc"],
+        "tests" => &["tests"],
+        _ => &[],
+    }
+}
+
+// ------------------------------------------------------ file tests
+
+#[cfg(test)]
+mod file_tests {
+    use std::path::Path;
+
+    use crate::file::ArtifactFile;
+
+    #[test]
+    fn main_artifact() {
+        assert_file("foo-1.0.jar", true, None, Some("jar"));
+        assert_file("foo-1.0.pom", true, None, Some("pom"));
+        assert_file("foo-1.0.jar.sha1", true, None, Some("jar.sha1"));
+    }
+
+    #[test]
+    fn classifier() {
+        assert_file("foo-1.0-sources.jar", true, Some("sources"), Some("jar"));
+        assert_file(
+            "foo-1.0-javadoc.jar.md5",
+            true,
+            Some("javadoc"),
+            Some("jar.md5"),
+        );
+        assert_file(
+            "foo-1.0-linux-x86_64.so",
+            true,
+            Some("linux-x86_64"),
+            Some("so"),
+        );
+        assert_file("foo-1.0-bin", true, Some("bin"), None);
+    }
+
+    #[test]
+    fn no_artifact() {
+        assert_file("_remote.repositories", false, None, None);
+        assert_file("maven-metadata-central.xml", false, None, None);
+        assert_file("bar-1.0.jar", false, None, None);
+    }
+
+    fn assert_file(name: &str, artifact: bool, classifier: Option<&str>, extension: Option<&str>) {
+        let file = ArtifactFile::new(Path::new(name), "foo", "1.0", false);
+        assert_eq!(name, file.name);
+        assert_eq!(artifact, file.artifact);
+        assert_eq!(classifier.map(String::from), file.classifier);
+        assert_eq!(extension.map(String::from), file.extension);
+    }
+}
@@ -1,65 +1,113 @@
-use std::path::Path;
-
+use anyhow::{bail, Result};
 use bit_vec::BitVec;
-use clap::ArgMatches;
 
 use Command::{Du, Keep, Remove};
 
 use crate::artifact::ArtifactFilter;
 use crate::command::Command;
+use crate::file::ClassifierFilter;
 use crate::group::GroupFilter;
+use crate::layout::Prefix;
+use crate::origin::{OriginFilter, RepositoryFilter};
+use crate::protect::Protection;
 use crate::repo::Gav;
 use crate::version::ReleaseType::{Releases, Snapshots};
-use crate::version::VersionRange::Exact;
+use crate::version::VersionRange::{Exact, Since, Until};
 use crate::version::{ReleaseType, VersionRange};
 
+// ------------------------------------------------------ filter
+
+// The filters selecting the versions and files of du, keep and rm. Use
+// Filter::builder() to construct a filter.
+#[derive(Default)]
 pub struct Filter {
     pub group_filter: Option<GroupFilter>,
     pub artifact_filter: Option<ArtifactFilter>,
     pub version_range: Option<VersionRange>,
     pub release_type: Option<ReleaseType>,
+    pub classifier_filter: Option<ClassifierFilter>,
+    pub origin_filter: Option<OriginFilter>,
+    pub repository_filter: Option<RepositoryFilter>,
+    pub prefix: Option<Prefix>,
+    // versions which don't follow the maven version scheme
+    pub opaque: bool,
+    pub protection: Option<Protection>,
 }
 
 impl Filter {
-    pub fn from(args: &ArgMatches, local_repo: &Path) -> Filter {
-        Filter {
-            group_filter: GroupFilter::from(args, local_repo),
-            artifact_filter: ArtifactFilter::from(args),
-            version_range: VersionRange::from(args),
-            release_type: ReleaseType::from(args),
-        }
+    pub fn builder() -> FilterBuilder {
+        FilterBuilder::default()
+    }
+
+    // Whether no filter selects anything. The protection doesn't count.
+    pub fn is_empty(&self) -> bool {
+        self.group_filter.is_none()
+            && self.artifact_filter.is_none()
+            && self.version_range.is_none()
+            && self.release_type.is_none()
+            && self.classifier_filter.is_none()
+            && self.origin_filter.is_none()
+            && self.repository_filter.is_none()
+            && self.prefix.is_none()
+            && !self.opaque
     }
 
     // combine given filters with &&
     pub fn conjunction(&self, gav: &Gav, command: &Command) -> bool {
+        // keep is limited to the regular versions or (with '--opaque') to the opaque versions
+        if matches!(command, Keep(_, _)) && gav.version.is_opaque() != self.opaque {
+            return false;
+        }
         let mut conditions = BitVec::new();
         if let Some(artifact_filter) = &self.artifact_filter {
             conditions.push(match command {
                 Keep(_, _) => !artifact_filter.match_artifact_id(gav.artifact.id.as_str()),
-                Remove(_, _) | Du(_, _, _) => {
-                    artifact_filter.match_artifact_id(gav.artifact.id.as_str())
-                }
+                Remove(_, _) | Du(_) => artifact_filter.match_artifact_id(gav.artifact.id.as_str()),
             });
         }
-        // if let Exact(version) = version_range {
-        if let Some(Exact(version)) = &self.version_range {
+        if let Some(version_range @ (Exact(_) | Since(_) | Until(_))) = &self.version_range {
             conditions.push(match command {
-                Keep(_, _) => *version != gav.version,
-                Remove(_, _) | Du(_, _, _) => *version == gav.version,
+                Keep(_, _) => version_range.excludes(&gav.version),
+                Remove(_, _) | Du(_) => version_range.contains(&gav.version),
             });
         }
         if let Some(release_type) = &self.release_type {
             match release_type {
                 Releases => conditions.push(match command {
                     Keep(_, _) => gav.version.snapshot,
-                    Remove(_, _) | Du(_, _, _) => !gav.version.snapshot,
+                    Remove(_, _) | Du(_) => !gav.version.snapshot,
                 }),
                 Snapshots => conditions.push(match command {
                     Keep(_, _) => !gav.version.snapshot,
-                    Remove(_, _) | Du(_, _, _) => gav.version.snapshot,
+                    Remove(_, _) | Du(_) => gav.version.snapshot,
                 }),
             }
         }
+        if let Some(origin_filter) = &self.origin_filter {
+            conditions.push(match command {
+                Keep(_, _) => !origin_filter.match_origin(&gav.version),
+                Remove(_, _) | Du(_) => origin_filter.match_origin(&gav.version),
+            });
+        }
+        if let Some(repository_filter) = &self.repository_filter {
+            conditions.push(match command {
+                Keep(_, _) => !repository_filter.match_repository(&gav.version),
+                Remove(_, _) | Du(_) => repository_filter.match_repository(&gav.version),
+            });
+        }
+        if let Some(prefix) = &self.prefix {
+            conditions.push(match command {
+                Keep(_, _) => gav.version.prefix != Some(*prefix),
+                Remove(_, _) | Du(_) => gav.version.prefix == Some(*prefix),
+            });
+        }
+        if self.opaque {
+            conditions.push(match command {
+                // see above
+                Keep(_, _) => true,
+                Remove(_, _) | Du(_) => gav.version.is_opaque(),
+            });
+        }
         if conditions.is_empty() {
             true
         } else {
@@ -67,3 +115,202 @@ impl Filter {
         }
     }
 }
+
+// ------------------------------------------------------ filter builder
+
+// Collects the filters in the syntax of the command line and parses them in
+// build(). If no protection is given, Cleanup uses the protection files of
+// the repository.
+#[derive(Default)]
+pub struct FilterBuilder {
+    groups: Option<String>,
+    artifacts: Option<String>,
+    versions: Option<String>,
+    release_type: Option<ReleaseType>,
+    classifiers: Option<String>,
+    attachments: Vec<String>,
+    origin: Option<String>,
+    repository_ids: Option<String>,
+    prefix: Option<Prefix>,
+    opaque: bool,
+    protection: Option<Protection>,
+}
+
+impl FilterBuilder {
+    // group ID like 'org.foo' including its subgroups
+    pub fn groups(mut self, groups: &str) -> FilterBuilder {
+        self.groups = Some(groups.to_string());
+        self
+    }
+
+    // glob pattern like 'foo-*'
+    pub fn artifacts(mut self, artifacts: &str) -> FilterBuilder {
+        self.artifacts = Some(artifacts.to_string());
+        self
+    }
+
+    // version range like '3..', '..3' or '1.0'
+    pub fn versions(mut self, versions: &str) -> FilterBuilder {
+        self.versions = Some(versions.to_string());
+        self
+    }
+
+    pub fn releases(mut self) -> FilterBuilder {
+        self.release_type = Some(Releases);
+        self
+    }
+
+    pub fn snapshots(mut self) -> FilterBuilder {
+        self.release_type = Some(Snapshots);
+        self
+    }
+
+    // comma separated glob patterns like 'sources,*javadoc'
+    pub fn classifiers(mut self, classifiers: &str) -> FilterBuilder {
+        self.classifiers = Some(classifiers.to_string());
+        self
+    }
+
+    // attachments like 'sources', 'javadoc' or 'tests'
+    pub fn attachment(mut self, attachment: &str) -> FilterBuilder {
+        self.attachments.push(attachment.to_string());
+        self
+    }
+
+    // 'local', 'remote' or a repository ID
+    pub fn origin(mut self, origin: &str) -> FilterBuilder {
+        self.origin = Some(origin.to_string());
+        self
+    }
+
+    // comma separated glob patterns like 'central,jboss-*'
+    pub fn repository_ids(mut self, repository_ids: &str) -> FilterBuilder {
+        self.repository_ids = Some(repository_ids.to_string());
+        self
+    }
+
+    pub fn installed(mut self) -> FilterBuilder {
+        self.prefix = Some(Prefix::Installed);
+        self
+    }
+
+    pub fn cached(mut self) -> FilterBuilder {
+        self.prefix = Some(Prefix::Cached);
+        self
+    }
+
+    // versions like 'latest' or '${revision}'
+    pub fn opaque(mut self) -> FilterBuilder {
+        self.opaque = true;
+        self
+    }
+
+    pub fn protection(mut self, protection: Protection) -> FilterBuilder {
+        self.protection = Some(protection);
+        self
+    }
+
+    pub fn build(self) -> Result<Filter> {
+        if self.classifiers.is_some() && !self.attachments.is_empty() {
+            bail!("Classifiers cannot be combined with attachments")
+        }
+        let classifier_filter = match self.classifiers {
+            Some(classifiers) => Some(ClassifierFilter::parse(classifiers.as_str())?),
+            None if !self.attachments.is_empty() => Some(ClassifierFilter::attachments(
+                self.attachments.iter().map(String::as_str),
+            )),
+            None => None,
+        };
+        Ok(Filter {
+            group_filter: self.groups.as_deref().map(GroupFilter::new),
+            artifact_filter: self
+                .artifacts
+                .as_deref()
+                .map(ArtifactFilter::parse)
+                .transpose()?,
+            version_range: self
+                .versions
+                .as_deref()
+                .map(VersionRange::parse)
+                .transpose()?,
+            release_type: self.release_type,
+            classifier_filter,
+            origin_filter: self.origin.as_deref().map(OriginFilter::parse),
+            repository_filter: self
+                .repository_ids
+                .as_deref()
+                .map(RepositoryFilter::parse)
+                .transpose()?,
+            prefix: self.prefix,
+            opaque: self.opaque,
+            protection: self.protection,
+        })
+    }
+}
+
+// ------------------------------------------------------ filter tests
+
+#[cfg(test)]
+mod filter_tests {
+    use crate::filter::Filter;
+    use crate::layout::Prefix;
+    use crate::origin::OriginFilter;
+    use crate::version::ReleaseType::Snapshots;
+
+    #[test]
+    fn builder() {
+        let filter = Filter::builder()
+            .groups("org.foo")
+            .artifacts("bar-*")
+            .versions("3..")
+            .snapshots()
+            .classifiers("sources, *javadoc")
+            .origin("central")
+            .repository_ids("jboss-*")
+            .installed()
+            .build()
+            .unwrap();
+        assert!(!filter.is_empty());
+        assert_eq!("org.foo", filter.group_filter.unwrap().group_id);
+        assert!(filter.artifact_filter.unwrap().match_artifact_id("bar-baz"));
+        assert_eq!(Some(Snapshots), filter.release_type);
+        assert_eq!(2, filter.classifier_filter.unwrap().classifiers.len());
+        assert_eq!(
+            Some(OriginFilter::Repository("central".to_string())),
+            filter.origin_filter
+        );
+        assert_eq!(Some(Prefix::Installed), filter.prefix);
+        assert!(Filter::builder().build().unwrap().is_empty());
+    }
+
+    #[test]
+    fn attachments() {
+        let filter = Filter::builder()
+            .attachment("sources")
+            .attachment("tests")
+            .build()
+            .unwrap();
+        assert_eq!(3, filter.classifier_filter.unwrap().classifiers.len());
+        assert!(Filter::builder()
+            .classifiers("sources")
+            .attachment("javadoc")
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Filter::builder().artifacts("[").build().is_err());
+        assert!(Filter::builder().versions("0..").build().is_err());
+        assert!(Filter::builder()
+            .versions("since:2024-02-31")
+            .build()
+            .is_err());
+        assert!(Filter::builder()
+            .versions("until:2023-13-01")
+            .build()
+            .is_err());
+        assert!(Filter::builder().classifiers("a,[").build().is_err());
+        assert!(Filter::builder().repository_ids("[").build().is_err());
+    }
+}
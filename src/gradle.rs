@@ -0,0 +1,314 @@
+use std::env;
+use std::fs::{remove_dir, remove_dir_all};
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+
+use anyhow::{Context, Result};
+use directories::BaseDirs;
+use log::debug;
+
+use crate::backend::{
+    flat_group_scopes, measure_version, prune_version_dir, walk_versions, Backend,
+};
+use crate::group::GroupFilter;
+use crate::origin::Origin;
+use crate::repo::{artifact_file_suffix, Gav};
+use crate::usage::DiskUsage;
+use crate::version::Version;
+
+const GRADLE_USER_HOME: &str = "GRADLE_USER_HOME";
+const MODULE_CACHE: &str = "caches/modules-2";
+const FILES: &str = "files-2.1";
+const METADATA_PREFIX: &str = "metadata-";
+const DESCRIPTORS: &str = "descriptors";
+
+// ------------------------------------------------------ gradle
+
+// The module cache of gradle uses the layout
+// 'modules-2/files-2.1/groupId/artifactId/version/<sha1>/artifactId-version[-classifier].ext'.
+// The group ID is one directory and each file lives in a directory named by
+// its SHA-1. The descriptors of a version are stored separately in
+// 'modules-2/metadata-<n>/descriptors/groupId/artifactId/version/'.
+#[derive(Debug, Clone)]
+pub struct Gradle {
+    pub root: PathBuf,
+    pub descriptors: Vec<PathBuf>,
+}
+
+impl Gradle {
+    pub fn new(path: &Path) -> Gradle {
+        Gradle {
+            root: path.join(FILES),
+            descriptors: descriptor_roots(path),
+        }
+    }
+
+    // Turns 'org.foo/bar' into the maven layout 'org/foo/bar' used by the group filter
+    fn walk_path(path: &Path) -> PathBuf {
+        let mut components = path.components();
+        let mut walk_path = match components.next() {
+            Some(group) => PathBuf::from(
+                group
+                    .as_os_str()
+                    .to_string_lossy()
+                    .replace('.', MAIN_SEPARATOR.to_string().as_str()),
+            ),
+            None => PathBuf::new(),
+        };
+        walk_path.push(components.as_path());
+        walk_path
+    }
+
+    fn version_descriptors(
+        &self,
+        group_id: &str,
+        artifact_id: &str,
+        version: &str,
+    ) -> Vec<PathBuf> {
+        self.descriptors
+            .iter()
+            .map(|descriptors| descriptors.join(group_id).join(artifact_id).join(version))
+            .filter(|path| path.is_dir())
+            .collect()
+    }
+}
+
+impl Backend for Gradle {
+    // The group ID is the name of the group directory. Gradle doesn't record
+    // the remote repository of a version, so the origin is unknown.
+    fn collect(&self, walk_predicate: &mut dyn FnMut(&Path) -> bool, visit: &mut dyn FnMut(Gav)) {
+        walk_versions(
+            self.root.as_path(),
+            |path| walk_predicate(&Gradle::walk_path(path)),
+            |path| {
+                if path.strip_prefix(&self.root).ok()?.components().count() == 3 {
+                    let pom = version_dir(path);
+                    if pom.is_none() {
+                        debug!("Skip '{}': No artifact files", path.display());
+                    }
+                    pom
+                } else {
+                    None
+                }
+            },
+            &mut |version_path, pom| {
+                let group_id = version_path
+                    .parent()
+                    .and_then(Path::parent)
+                    .and_then(Path::file_name)
+                    .and_then(|name| name.to_str());
+                if let Some(Ok(mut gav)) = group_id.map(|group_id| Gav::new(group_id, version_path))
+                {
+                    gav.version.pom = pom;
+                    gav.version.origin = Origin::default();
+                    gav.version.descriptors = self.version_descriptors(
+                        gav.group.id.as_str(),
+                        gav.artifact.id.as_str(),
+                        version_path
+                            .file_name()
+                            .and_then(|name| name.to_str())
+                            .unwrap_or(""),
+                    );
+                    visit(gav);
+                }
+            },
+        );
+    }
+
+    // The descriptors count for the version
+    fn measure(&self, version: &mut Version, artifact_id: &str, disk_usage: &mut DiskUsage) {
+        let descriptors = version.descriptors.clone();
+        measure_version(version, artifact_id, 2, &descriptors, disk_usage);
+    }
+
+    fn version_paths(&self, version: &Version) -> Vec<PathBuf> {
+        std::iter::once(&version.path)
+            .chain(&version.descriptors)
+            .cloned()
+            .collect()
+    }
+
+    fn remove(&self, version: &Version) -> Result<()> {
+        remove_dir_all(version.path.as_path())?;
+        for descriptor in &version.descriptors {
+            remove_dir_all(descriptor)?;
+        }
+        Ok(())
+    }
+
+    // Removes the SHA-1 directories of removed files as well
+    fn prune(&self, version: &Version) {
+        for file in &version.files {
+            if let Some(hash_path) = file.path.parent() {
+                let _ = remove_dir(hash_path);
+            }
+        }
+        prune_version_dir(version.path.as_path());
+        for descriptor in &version.descriptors {
+            prune_version_dir(descriptor);
+        }
+    }
+
+    // The descriptors of a version belong to the version as well
+    fn version_dir(&self, path: &Path) -> bool {
+        let Some(base) = path.parent().and_then(Path::parent).and_then(Path::parent) else {
+            return false;
+        };
+        if base == self.root {
+            version_dir(path).is_some()
+        } else if self
+            .descriptors
+            .iter()
+            .any(|descriptors| descriptors == base)
+        {
+            path.strip_prefix(base)
+                .is_ok_and(|version_path| version_dir(&self.root.join(version_path)).is_some())
+        } else {
+            false
+        }
+    }
+
+    fn group_paths(&self, group_id: &str) -> Vec<PathBuf> {
+        std::iter::once(&self.root)
+            .chain(self.descriptors.iter())
+            .map(|path| path.join(group_id))
+            .collect()
+    }
+
+    fn group_scopes(&self, group_filter: &GroupFilter) -> Vec<PathBuf> {
+        flat_group_scopes(
+            std::iter::once(&self.root).chain(self.descriptors.iter()),
+            group_filter,
+        )
+    }
+}
+
+// Returns '$GRADLE_USER_HOME/caches/modules-2' or '~/.gradle/caches/modules-2'
+pub fn module_cache() -> Result<PathBuf> {
+    let gradle_user_home = match env::var_os(GRADLE_USER_HOME) {
+        Some(gradle_user_home) => PathBuf::from(gradle_user_home),
+        None => BaseDirs::new()
+            .with_context(|| "No home directory")?
+            .home_dir()
+            .join(".gradle"),
+    };
+    Ok(gradle_user_home.join(MODULE_CACHE))
+}
+
+pub fn is_module_cache(path: &Path) -> bool {
+    path.join(FILES).is_dir()
+}
+
+// Returns the 'metadata-<n>/descriptors' directories of the module cache
+fn descriptor_roots(path: &Path) -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = match path.read_dir() {
+        Ok(entries) => entries
+            .flatten()
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| name.starts_with(METADATA_PREFIX))
+            })
+            .map(|entry| entry.path().join(DESCRIPTORS))
+            .filter(|path| path.is_dir())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    roots.sort();
+    roots
+}
+
+// Checks whether the directory is a version of the module cache. The directory
+// must be named like a valid version and contain at least one artifact file
+// inside a SHA-1 directory. Returns whether the version contains a pom or a
+// gradle module descriptor.
+fn version_dir(path: &Path) -> Option<bool> {
+    let version = Version::from_path(path).ok()?;
+    let version_name = path.file_name()?.to_str()?;
+    let artifact_id = path.parent()?.file_name()?.to_str()?;
+    let mut artifact_files = false;
+    let mut pom = false;
+    for hash_entry in path.read_dir().ok()?.flatten() {
+        if !hash_entry
+            .file_type()
+            .is_ok_and(|file_type| file_type.is_dir())
+        {
+            continue;
+        }
+        let Ok(entries) = hash_entry.path().read_dir() else {
+            continue;
+        };
+        for dir_entry in entries.flatten() {
+            if let Some(file_name) = dir_entry.file_name().to_str() {
+                if let Some(suffix) =
+                    artifact_file_suffix(artifact_id, version_name, version.snapshot, file_name)
+                {
+                    artifact_files = true;
+                    pom = pom || suffix == ".pom" || suffix == ".module";
+                }
+            }
+        }
+    }
+    if artifact_files {
+        Some(pom)
+    } else {
+        None
+    }
+}
+
+// ------------------------------------------------------ gradle tests
+
+#[cfg(test)]
+mod gradle_tests {
+    use std::path::{Path, PathBuf};
+
+    use crate::backend::Backend;
+    use crate::gradle::{descriptor_roots, version_dir, Gradle};
+    use crate::temp::TempDir;
+
+    #[test]
+    fn versions() {
+        let cache = TempDir::new("gradle-versions");
+        cache.file("files-2.1/org.foo/bar/1.0/0a1b/bar-1.0.jar", "");
+        cache.file("files-2.1/org.foo/bar/1.0/2c3d/bar-1.0.pom", "");
+        cache.file("files-2.1/org.foo/bar/2.0/4e5f/bar-2.0-sources.jar", "");
+        cache.file("files-2.1/org.foo/bar/3.0/6a7b/other-3.0.jar", "");
+
+        let files = cache.join("files-2.1/org.foo/bar");
+        assert_eq!(Some(true), version_dir(&files.join("1.0")));
+        assert_eq!(Some(false), version_dir(&files.join("2.0")));
+        assert_eq!(None, version_dir(&files.join("3.0")));
+        assert_eq!(None, version_dir(&files));
+    }
+
+    #[test]
+    fn descriptors() {
+        let cache = TempDir::new("gradle-descriptors");
+        cache.file("files-2.1/org.foo/bar/1.0/0a1b/bar-1.0.jar", "");
+        cache.dir("metadata-2.97/descriptors/org.foo/bar/1.0/9f8e");
+        cache.dir("metadata-2.106/descriptors/org.foo/bar/1.0/9f8e");
+
+        assert_eq!(
+            vec![
+                cache.join("metadata-2.106/descriptors"),
+                cache.join("metadata-2.97/descriptors"),
+            ],
+            descriptor_roots(&cache.path)
+        );
+        let gradle = Gradle::new(&cache.path);
+        assert_eq!(cache.join("files-2.1"), gradle.root);
+        assert_eq!(
+            PathBuf::from("org/foo/bar"),
+            Gradle::walk_path(Path::new("org.foo/bar"))
+        );
+        assert_eq!(
+            vec![
+                cache.join("metadata-2.106/descriptors/org.foo/bar/1.0"),
+                cache.join("metadata-2.97/descriptors/org.foo/bar/1.0"),
+            ],
+            gradle.version_descriptors("org.foo", "bar", "1.0")
+        );
+        assert!(gradle.version_dir(&cache.join("metadata-2.97/descriptors/org.foo/bar/1.0")));
+        assert!(!gradle.version_dir(&cache.join("metadata-2.97/descriptors/org.foo/bar")));
+    }
+}
@@ -2,9 +2,6 @@ use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 use std::path::{Path, PathBuf, MAIN_SEPARATOR};
 
-use clap::ArgMatches;
-use walkdir::DirEntry;
-
 use crate::artifact::Artifact;
 
 // ------------------------------------------------------ group
@@ -15,6 +12,10 @@ pub struct Group {
     pub path: PathBuf,
     pub artifacts: BTreeMap<String, Artifact>,
     pub bytes: u64,
+    pub apparent_bytes: u64,
+    // files which are not part of a version (du --all only)
+    pub unaccounted_bytes: u64,
+    pub unaccounted_apparent_bytes: u64,
 }
 
 impl Group {
@@ -24,6 +25,9 @@ impl Group {
             path: path.to_path_buf(),
             artifacts: BTreeMap::new(),
             bytes: 0,
+            apparent_bytes: 0,
+            unaccounted_bytes: 0,
+            unaccounted_apparent_bytes: 0,
         }
     }
 }
@@ -44,32 +48,26 @@ impl Display for Group {
 
 // ------------------------------------------------------ group filter
 
+// The group path is relative to the roots of the repository.
 pub struct GroupFilter {
     pub group_id: String,
     pub group_path: PathBuf,
 }
 
 impl GroupFilter {
-    pub fn from(args: &ArgMatches, local_repo: &Path) -> Option<GroupFilter> {
-        if let Some(group_id) = args.get_one::<String>("groups") {
-            let group_path =
-                local_repo.join(group_id.replace('.', MAIN_SEPARATOR.to_string().as_str()));
-            Some(GroupFilter {
-                group_id: group_id.to_string(),
-                group_path,
-            })
-        } else {
-            None
+    pub fn new(group_id: &str) -> GroupFilter {
+        GroupFilter {
+            group_id: group_id.to_string(),
+            group_path: PathBuf::from(group_id.replace('.', MAIN_SEPARATOR.to_string().as_str())),
         }
     }
 
-    pub fn subgroup_of(&self, dir_entry: &DirEntry) -> bool {
-        self.group_path.starts_with(dir_entry.path())
-            || dir_entry.path().starts_with(self.group_path.as_path())
+    pub fn subgroup_of(&self, path: &Path) -> bool {
+        self.group_path.starts_with(path) || path.starts_with(self.group_path.as_path())
     }
 
-    pub fn no_subgroup_of(&self, dir_entry: &DirEntry) -> bool {
-        !dir_entry.path().starts_with(self.group_path.as_path())
+    pub fn no_subgroup_of(&self, path: &Path) -> bool {
+        !path.starts_with(self.group_path.as_path())
     }
 
     pub fn match_group_id(&self, group_id: &str) -> bool {
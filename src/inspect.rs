@@ -0,0 +1,72 @@
+use std::io::stdout;
+
+use anyhow::{bail, Result};
+use clap::ArgMatches;
+use mcup::cleanup::Cleanup;
+use mcup::command::Command::Du;
+use mcup::command::DuOptions;
+use mcup::filter::Filter;
+use mcup::output;
+use mcup::repo::Repository;
+use mcup::version::Version;
+
+use crate::cli;
+
+// ------------------------------------------------------ versions
+
+// Lists the versions of 'groupId:artifactId' in the located repository
+pub fn versions(args: &ArgMatches, sub_args: &ArgMatches) -> Result<()> {
+    if args.contains_id("discover") {
+        bail!("The subcommand 'versions' cannot be used with '--discover'")
+    }
+    let coordinates = sub_args.get_one::<String>("coordinates").unwrap();
+    let Some((group_id, artifact_id)) = coordinates
+        .split_once(':')
+        .filter(|(group_id, artifact_id)| !group_id.is_empty() && !artifact_id.is_empty())
+    else {
+        bail!(
+            "Invalid coordinates '{}'. Use 'groupId:artifactId'.",
+            coordinates
+        )
+    };
+    let repository = Repository::locate(&cli::location(args))?;
+    if !repository.exists() {
+        bail!(
+            "Local repository does not exist: '{}'",
+            repository.path.display()
+        )
+    }
+    let report = Cleanup::new(repository, Du(DuOptions::default()))
+        .filter(
+            Filter::builder()
+                .groups(group_id)
+                .artifacts(artifact_id)
+                .build()?,
+        )
+        .run()?;
+    let versions: Vec<&Version> = match report
+        .repository
+        .groups
+        .get(group_id)
+        .and_then(|group| group.artifacts.get(artifact_id))
+    {
+        Some(artifact) => artifact.versions.values().rev().collect(),
+        None => bail!("No versions of '{}' found", coordinates),
+    };
+    output::versions(&mut stdout(), group_id, artifact_id, &versions)?;
+    Ok(())
+}
+
+// ------------------------------------------------------ version-compare
+
+pub fn compare(sub_args: &ArgMatches) -> Result<()> {
+    let a = sub_args.get_one::<String>("a").unwrap();
+    let b = sub_args.get_one::<String>("b").unwrap();
+    output::version_compare(&mut stdout(), (a, &version(a)), (b, &version(b)))?;
+    Ok(())
+}
+
+// Versions which don't follow the maven version scheme are compared as opaque versions
+fn version(version: &str) -> Version {
+    Version::parse(version).unwrap_or_else(|_| Version::opaque(version))
+}
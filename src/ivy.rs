@@ -0,0 +1,670 @@
+use std::collections::BTreeSet;
+use std::fs::{read_to_string, remove_dir, remove_dir_all, remove_file};
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+
+use anyhow::{Context, Result};
+use directories::BaseDirs;
+use quick_xml::events::Event;
+use quick_xml::{Reader, XmlVersion};
+use walkdir::WalkDir;
+
+use crate::artifact::Artifact;
+use crate::backend::{flat_group_scopes, prune_version_dir, Backend};
+use crate::file::ArtifactFile;
+use crate::group::{Group, GroupFilter};
+use crate::origin::Origin;
+use crate::repo::{artifact_file_suffix, Gav};
+use crate::usage::DiskUsage;
+use crate::version::Version;
+
+const IVY_HOME: &str = ".ivy2";
+const CACHE: &str = "cache";
+const LOCAL: &str = "local";
+// descriptors in the cache: 'ivy-<revision>.xml' and 'ivydata-<revision>.properties'
+const DESCRIPTOR_PREFIX: &str = "ivy-";
+const DESCRIPTOR_SUFFIX: &str = ".xml";
+const ORIGINAL_SUFFIX: &str = ".original";
+const DATA_PREFIX: &str = "ivydata-";
+const DATA_SUFFIX: &str = ".properties";
+const RESOLVER: &str = "resolver=";
+// descriptor in the local repository: '<revision>/ivys/ivy.xml'
+const IVYS: &str = "ivys";
+const IVY_XML: &str = "ivy.xml";
+
+// ------------------------------------------------------ ivy
+
+// The cache and local repository of ivy (used by sbt and ant). Both use the
+// organisation as one directory:
+//   - cache: 'organisation/module/ivy-<revision>.xml' and
+//     'organisation/module/<type>s/<artifact>-<revision>[-classifier].ext'.
+//     The files of all revisions share the directories of the module.
+//   - local: 'organisation/module/<revision>/ivys/ivy.xml' and
+//     'organisation/module/<revision>/<type>s/<artifact>[-classifier].ext'
+// The revisions are taken from the descriptors, the names of the artifacts
+// from the publications in the descriptors.
+#[derive(Debug, Clone)]
+pub struct Ivy {
+    pub roots: Vec<IvyRoot>,
+}
+
+#[derive(Debug, Clone)]
+pub struct IvyRoot {
+    pub path: PathBuf,
+    pub kind: Kind,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Kind {
+    Cache,
+    Local,
+}
+
+impl Ivy {
+    // '~/.ivy2' contains the roots 'cache/' and 'local/'. Each of them can be used on its own.
+    pub fn new(path: &Path) -> Ivy {
+        let roots = if path.file_name().is_some_and(|name| name == IVY_HOME) {
+            [(CACHE, Kind::Cache), (LOCAL, Kind::Local)]
+                .into_iter()
+                .map(|(name, kind)| IvyRoot {
+                    path: path.join(name),
+                    kind,
+                })
+                .filter(|root| root.path.is_dir())
+                .collect()
+        } else {
+            let kind = if path.file_name().is_some_and(|name| name == LOCAL) {
+                Kind::Local
+            } else {
+                Kind::Cache
+            };
+            vec![IvyRoot {
+                path: path.to_path_buf(),
+                kind,
+            }]
+        };
+        Ivy { roots }
+    }
+
+    fn cache_root(&self, path: &Path) -> bool {
+        self.roots
+            .iter()
+            .any(|root| root.kind == Kind::Cache && root.path == path)
+    }
+}
+
+// Returns '~/.ivy2'
+pub fn ivy_home() -> Result<PathBuf> {
+    Ok(BaseDirs::new()
+        .with_context(|| "No home directory")?
+        .home_dir()
+        .join(IVY_HOME))
+}
+
+// '~/.ivy2', '~/.ivy2/cache' or '~/.ivy2/local'
+pub fn is_ivy(path: &Path) -> bool {
+    let name = |path: &Path| {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .map(String::from)
+    };
+    match name(path).as_deref() {
+        Some(IVY_HOME) => true,
+        Some(CACHE) | Some(LOCAL) => path.parent().and_then(name).as_deref() == Some(IVY_HOME),
+        _ => false,
+    }
+}
+
+impl Backend for Ivy {
+    // The group ID is the name of the organisation directory. Versions in the
+    // cache have been downloaded by the resolver in 'ivydata-<revision>.properties'.
+    fn collect(&self, walk_predicate: &mut dyn FnMut(&Path) -> bool, visit: &mut dyn FnMut(Gav)) {
+        for root in &self.roots {
+            for (organisation, organisation_path) in sub_dirs(&root.path) {
+                let group_path =
+                    PathBuf::from(organisation.replace('.', MAIN_SEPARATOR.to_string().as_str()));
+                if !walk_predicate(&group_path) {
+                    continue;
+                }
+                for (module, module_path) in sub_dirs(&organisation_path) {
+                    if !walk_predicate(&group_path.join(&module)) {
+                        continue;
+                    }
+                    let revisions: Vec<(String, PathBuf, Origin)> = match root.kind {
+                        Kind::Cache => cached_revisions(&module_path)
+                            .into_iter()
+                            .map(|revision| {
+                                let origin = cached_origin(&module_path, &revision.revision);
+                                (revision.revision, revision.descriptor, origin)
+                            })
+                            .collect(),
+                        Kind::Local => local_revisions(&module_path)
+                            .into_iter()
+                            .map(|(revision, path)| {
+                                let origin = Origin {
+                                    local: true,
+                                    ..Origin::default()
+                                };
+                                (revision, path, origin)
+                            })
+                            .collect(),
+                    };
+                    for (revision, version_path, origin) in revisions {
+                        let mut version = Version::parse(revision.as_str())
+                            .unwrap_or_else(|_| Version::opaque(revision.as_str()));
+                        version.path = version_path;
+                        version.origin = origin;
+                        visit(Gav {
+                            group: Group::new(organisation.as_str(), &organisation_path),
+                            artifact: Artifact::new(module.as_str(), &module_path),
+                            version,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    // The files of a cached revision are the descriptors and the artifacts
+    // assigned to the revision. A local revision is measured like a maven version.
+    fn measure(&self, version: &mut Version, artifact_id: &str, disk_usage: &mut DiskUsage) {
+        let mut bytes: u64 = 0;
+        let mut apparent_bytes: u64 = 0;
+        let mut files: Vec<ArtifactFile> = Vec::new();
+        let mut add = |path: &Path, suffix: Option<&str>, artifact_file: bool| {
+            if let Ok(meta) = path.symlink_metadata() {
+                if meta.file_type().is_symlink() {
+                    return;
+                }
+                let (file_bytes, file_apparent_bytes) = disk_usage.file(&meta);
+                bytes += file_bytes;
+                apparent_bytes += file_apparent_bytes;
+                if artifact_file {
+                    let mut file = ArtifactFile::with_suffix(path, suffix);
+                    file.bytes = file_bytes;
+                    file.apparent_bytes = file_apparent_bytes;
+                    files.push(file);
+                }
+            }
+        };
+
+        match cached_revision(version) {
+            Some(revision) => {
+                for path in &revision.descriptors {
+                    add(path, None, true);
+                }
+                for (path, suffix) in &revision.artifacts {
+                    add(path, Some(suffix.as_str()), true);
+                }
+            }
+            None => {
+                let names = Descriptor::read(&version.path.join(IVYS).join(IVY_XML))
+                    .map(|descriptor| descriptor.artifacts)
+                    .unwrap_or_default();
+                for dir_entry in WalkDir::new(version.path.as_path())
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                {
+                    let artifact_file = dir_entry.depth() == 2 && dir_entry.file_type().is_file();
+                    let descriptor = dir_entry
+                        .path()
+                        .parent()
+                        .is_some_and(|parent| parent.ends_with(IVYS));
+                    let suffix = if artifact_file && !descriptor {
+                        dir_entry
+                            .file_name()
+                            .to_str()
+                            .and_then(|name| local_suffix(&names, artifact_id, name))
+                    } else {
+                        None
+                    };
+                    add(dir_entry.path(), suffix, artifact_file);
+                }
+            }
+        }
+        version.bytes = bytes;
+        version.apparent_bytes = apparent_bytes;
+        version.files = files;
+    }
+
+    fn version_paths(&self, version: &Version) -> Vec<PathBuf> {
+        match cached_revision(version) {
+            Some(revision) => revision
+                .descriptors
+                .into_iter()
+                .chain(revision.artifacts.into_iter().map(|(path, _)| path))
+                .collect(),
+            None => vec![version.path.clone()],
+        }
+    }
+
+    fn remove(&self, version: &Version) -> Result<()> {
+        match cached_revision(version) {
+            Some(_) => {
+                for path in self.version_paths(version) {
+                    remove_file(path)?;
+                }
+                Ok(())
+            }
+            None => Ok(remove_dir_all(version.path.as_path())?),
+        }
+    }
+
+    // Removes the '<type>s' directories left empty as well
+    fn prune(&self, version: &Version) {
+        for file in &version.files {
+            if let Some(parent) = file.path.parent() {
+                if parent != version.path {
+                    let _ = remove_dir(parent);
+                }
+            }
+        }
+        prune_version_dir(version.path.as_path());
+    }
+
+    fn version_dir(&self, path: &Path) -> bool {
+        path.join(IVYS).join(IVY_XML).is_file()
+    }
+
+    // The descriptors and artifacts of the revisions in the cache
+    fn version_file(&self, path: &Path) -> bool {
+        // 'module/ivy-<revision>.xml' or 'module/<type>s/<file>'
+        let module_path = path.ancestors().skip(1).take(2).find(|module_path| {
+            module_path
+                .parent()
+                .and_then(Path::parent)
+                .is_some_and(|root| self.cache_root(root))
+        });
+        module_path.is_some_and(|module_path| {
+            cached_revisions(module_path).iter().any(|revision| {
+                revision
+                    .descriptors
+                    .iter()
+                    .any(|descriptor| descriptor == path)
+                    || revision
+                        .artifacts
+                        .iter()
+                        .any(|(artifact, _)| artifact == path)
+            })
+        })
+    }
+
+    fn group_paths(&self, group_id: &str) -> Vec<PathBuf> {
+        self.roots
+            .iter()
+            .map(|root| root.path.join(group_id))
+            .collect()
+    }
+
+    fn group_scopes(&self, group_filter: &GroupFilter) -> Vec<PathBuf> {
+        flat_group_scopes(self.roots.iter().map(|root| &root.path), group_filter)
+    }
+}
+
+// Returns the names and paths of the directories inside 'path'
+fn sub_dirs(path: &Path) -> Vec<(String, PathBuf)> {
+    let mut dirs: Vec<(String, PathBuf)> = match path.read_dir() {
+        Ok(entries) => entries
+            .flatten()
+            .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+            .filter_map(|entry| {
+                entry
+                    .file_name()
+                    .to_str()
+                    .map(|name| (name.to_string(), entry.path()))
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    dirs.sort();
+    dirs
+}
+
+// ------------------------------------------------------ cache
+
+// A revision in the cache. 'descriptor' is the path of 'ivy-<revision>.xml'
+// and used as the path of the version.
+#[derive(Debug)]
+struct CachedRevision {
+    revision: String,
+    descriptor: PathBuf,
+    descriptors: Vec<PathBuf>,
+    artifacts: Vec<(PathBuf, String)>,
+}
+
+fn cached_revision(version: &Version) -> Option<CachedRevision> {
+    let module_path = version.path.parent()?;
+    cached_revisions(module_path)
+        .into_iter()
+        .find(|revision| revision.descriptor == version.path)
+}
+
+// Returns the revisions of a module in the cache. The artifact files of all
+// revisions share the '<type>s' directories. A file belongs to the revision
+// with the longest matching name, so that 'foo-1.0.1.jar' belongs to '1.0.1',
+// not to '1.0'.
+fn cached_revisions(module_path: &Path) -> Vec<CachedRevision> {
+    let Ok(entries) = module_path.read_dir() else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = Vec::new();
+    let mut revisions: Vec<(CachedRevision, Vec<String>)> = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+            if let Ok(type_entries) = path.read_dir() {
+                files.extend(
+                    type_entries
+                        .flatten()
+                        .filter(|entry| {
+                            entry.file_type().is_ok_and(|file_type| file_type.is_file())
+                        })
+                        .map(|entry| entry.path()),
+                );
+            }
+            continue;
+        }
+        let Some(revision) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.strip_prefix(DESCRIPTOR_PREFIX))
+            .and_then(|name| name.strip_suffix(DESCRIPTOR_SUFFIX))
+            .map(String::from)
+        else {
+            continue;
+        };
+        let descriptor = Descriptor::read(&path).unwrap_or_default();
+        let module = module_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("");
+        let names = if descriptor.artifacts.is_empty() {
+            vec![module.to_string()]
+        } else {
+            descriptor.artifacts
+        };
+        let descriptors = [
+            path.clone(),
+            module_path.join(format!(
+                "{DESCRIPTOR_PREFIX}{revision}{DESCRIPTOR_SUFFIX}{ORIGINAL_SUFFIX}"
+            )),
+            module_path.join(format!("{DATA_PREFIX}{revision}{DATA_SUFFIX}")),
+        ]
+        .into_iter()
+        .filter(|path| path.is_file())
+        .collect();
+        revisions.push((
+            CachedRevision {
+                revision,
+                descriptor: path,
+                descriptors,
+                artifacts: Vec::new(),
+            },
+            names,
+        ));
+    }
+
+    for file in files {
+        let Some(file_name) = file.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let best = revisions
+            .iter_mut()
+            .filter_map(|(revision, names)| {
+                let snapshot = revision.revision.ends_with("-SNAPSHOT");
+                names
+                    .iter()
+                    .find_map(|name| {
+                        artifact_file_suffix(name, &revision.revision, snapshot, file_name)
+                    })
+                    .map(|suffix| (suffix.to_string(), revision))
+            })
+            .max_by_key(|(suffix, _)| std::cmp::Reverse(suffix.len()));
+        if let Some((suffix, revision)) = best {
+            revision.artifacts.push((file.clone(), suffix));
+        }
+    }
+    revisions
+        .into_iter()
+        .map(|(revision, _)| revision)
+        .collect()
+}
+
+// Reads the resolver from 'ivydata-<revision>.properties'. The resolver 'local'
+// refers to the local repository.
+fn cached_origin(module_path: &Path, revision: &str) -> Origin {
+    let data = module_path.join(format!("{DATA_PREFIX}{revision}{DATA_SUFFIX}"));
+    let resolver = read_to_string(data).ok().and_then(|content| {
+        content
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(RESOLVER).map(String::from))
+    });
+    match resolver.as_deref() {
+        Some("local") => Origin {
+            local: true,
+            ..Origin::default()
+        },
+        Some(resolver) if !resolver.is_empty() => Origin {
+            local: false,
+            repositories: BTreeSet::from([resolver.to_string()]),
+        },
+        _ => Origin::default(),
+    }
+}
+
+// ------------------------------------------------------ local
+
+// Returns the revisions of a module in the local repository. sbt plugins add
+// 'scala_<version>/sbt_<version>/' between the module and the revision.
+fn local_revisions(module_path: &Path) -> Vec<(String, PathBuf)> {
+    let mut revisions = Vec::new();
+    let mut it = WalkDir::new(module_path)
+        .min_depth(1)
+        .max_depth(3)
+        .into_iter();
+    while let Some(entry) = it.next() {
+        let Ok(entry) = entry else { continue };
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+        let descriptor_path = entry.path().join(IVYS).join(IVY_XML);
+        if descriptor_path.is_file() {
+            let revision = Descriptor::read(&descriptor_path)
+                .and_then(|descriptor| descriptor.revision)
+                .or_else(|| entry.file_name().to_str().map(String::from));
+            if let Some(revision) = revision {
+                revisions.push((revision, entry.path().to_path_buf()));
+            }
+            it.skip_current_dir();
+        }
+    }
+    revisions
+}
+
+// Returns '[-classifier].ext' of '<artifact>[-classifier].ext'. The artifact
+// name defaults to the module.
+fn local_suffix<'a>(names: &[String], module: &str, file_name: &'a str) -> Option<&'a str> {
+    names
+        .iter()
+        .map(String::as_str)
+        .chain(names.is_empty().then_some(module))
+        .filter_map(|name| file_name.strip_prefix(name))
+        .filter(|suffix| suffix.starts_with('.') || suffix.starts_with('-'))
+        .min_by_key(|suffix| suffix.len())
+}
+
+// ------------------------------------------------------ descriptor
+
+// The parts of an ivy descriptor used by mcup:
+// <info revision="1.0"/> and <publications><artifact name="foo"/></publications>
+#[derive(Debug, Default)]
+struct Descriptor {
+    revision: Option<String>,
+    artifacts: Vec<String>,
+}
+
+impl Descriptor {
+    fn read(path: &Path) -> Option<Descriptor> {
+        Some(Descriptor::parse(read_to_string(path).ok()?.as_str()))
+    }
+
+    fn parse(content: &str) -> Descriptor {
+        let mut reader = Reader::from_str(content);
+        reader.config_mut().trim_text(true);
+
+        let mut descriptor = Descriptor::default();
+        let mut publications = false;
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                    let attribute = |name: &[u8]| {
+                        e.attributes()
+                            .flatten()
+                            .find(|attribute| attribute.key.as_ref() == name)
+                            .and_then(|attribute| {
+                                attribute
+                                    .normalized_value(XmlVersion::Implicit1_0)
+                                    .ok()
+                                    .map(|v| v.into_owned())
+                            })
+                    };
+                    match e.name().as_ref() {
+                        b"info" => descriptor.revision = attribute(b"revision"),
+                        b"publications" => publications = true,
+                        b"artifact" if publications => {
+                            if let Some(name) = attribute(b"name") {
+                                if !descriptor.artifacts.contains(&name) {
+                                    descriptor.artifacts.push(name);
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(Event::End(e)) if e.name().as_ref() == b"publications" => {
+                    publications = false;
+                }
+                Ok(Event::Eof) | Err(_) => break,
+                _ => {}
+            }
+        }
+        descriptor
+    }
+}
+
+// ------------------------------------------------------ ivy tests
+
+#[cfg(test)]
+mod ivy_tests {
+    use std::path::{Path, PathBuf};
+
+    use crate::backend::Backend;
+    use crate::ivy::{cached_revisions, is_ivy, Descriptor, Ivy};
+    use crate::repo::Gav;
+    use crate::temp::TempDir;
+
+    #[test]
+    fn publications() {
+        let descriptor = Descriptor::parse(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<ivy-module version="2.0" xmlns:e="http://ant.apache.org/ivy/extra">
+    <info organisation="org.foo" module="bar" revision="1.0" status="release"/>
+    <configurations><conf name="default"/></configurations>
+    <publications>
+        <artifact name="bar" type="jar" ext="jar" conf="default"/>
+        <artifact name="bar" type="src" ext="jar" conf="sources" e:classifier="sources"/>
+        <artifact name="bar-core" type="jar" ext="jar" conf="default"/>
+    </publications>
+    <dependencies>
+        <dependency org="org.baz" name="baz" rev="2.0"><artifact name="baz-all"/></dependency>
+    </dependencies>
+</ivy-module>"#,
+        );
+        assert_eq!(Some("1.0".to_string()), descriptor.revision);
+        assert_eq!(vec!["bar", "bar-core"], descriptor.artifacts);
+    }
+
+    #[test]
+    fn ivy_paths() {
+        assert!(is_ivy(Path::new("/home/foo/.ivy2")));
+        assert!(is_ivy(Path::new("/home/foo/.ivy2/cache")));
+        assert!(is_ivy(Path::new("/home/foo/.ivy2/local")));
+        assert!(!is_ivy(Path::new("/home/foo/.m2/repository")));
+        assert!(!is_ivy(Path::new("/home/foo/cache")));
+    }
+
+    #[test]
+    fn cache() {
+        let temp = TempDir::new("ivy-cache");
+        let home = temp.join(".ivy2");
+        let module = home.join("cache/org.foo/bar");
+        temp.file(module.join("ivy-1.0.xml"), ivy_xml("1.0"));
+        temp.file(module.join("ivy-1.0.xml.original"), "");
+        temp.file(
+            module.join("ivydata-1.0.properties"),
+            "resolver=sbt-chain\n",
+        );
+        temp.file(module.join("ivy-1.0.1.xml"), ivy_xml("1.0.1"));
+        temp.file(module.join("jars/bar-1.0.jar"), "");
+        temp.file(module.join("srcs/bar-1.0-sources.jar"), "");
+        temp.file(module.join("jars/bar-1.0.1.jar"), "");
+        temp.file(module.join("jars/other-1.0.jar"), "");
+
+        let mut revisions = cached_revisions(&module);
+        revisions.sort_by(|a, b| a.revision.cmp(&b.revision));
+        assert_eq!(2, revisions.len());
+        assert_eq!(3, revisions[0].descriptors.len());
+        assert_eq!(
+            vec![
+                module.join("jars/bar-1.0.jar"),
+                module.join("srcs/bar-1.0-sources.jar")
+            ],
+            sorted(revisions[0].artifacts.iter().map(|(path, _)| path.clone()))
+        );
+        assert_eq!(
+            vec![module.join("jars/bar-1.0.1.jar")],
+            sorted(revisions[1].artifacts.iter().map(|(path, _)| path.clone()))
+        );
+
+        let ivy = Ivy::new(&home);
+        let gavs = collect(&ivy);
+        assert_eq!(vec!["org.foo:bar:1.0", "org.foo:bar:1.0.1"], gavs);
+        assert!(ivy.version_file(&module.join("jars/bar-1.0.1.jar")));
+        assert!(ivy.version_file(&module.join("ivydata-1.0.properties")));
+        assert!(!ivy.version_file(&module.join("jars/other-1.0.jar")));
+    }
+
+    #[test]
+    fn local() {
+        let temp = TempDir::new("ivy-local");
+        let home = temp.join(".ivy2");
+        let revision = home.join("local/org.foo/bar/1.0");
+        temp.file(revision.join("ivys/ivy.xml"), ivy_xml("1.0"));
+        temp.file(revision.join("jars/bar.jar"), "");
+        let plugin = home.join("local/org.foo/plugin/scala_2.12/sbt_1.0/2.0");
+        temp.file(plugin.join("ivys/ivy.xml"), ivy_xml("2.0"));
+
+        let ivy = Ivy::new(&home.join("local"));
+        assert_eq!(vec!["org.foo:bar:1.0", "org.foo:plugin:2.0"], collect(&ivy));
+        assert!(ivy.version_dir(&revision));
+        assert!(!ivy.version_dir(&revision.join("jars")));
+    }
+
+    fn ivy_xml(revision: &str) -> String {
+        format!(
+            r#"<ivy-module version="2.0"><info organisation="org.foo" module="bar" revision="{revision}"/></ivy-module>"#
+        )
+    }
+
+    fn collect(ivy: &Ivy) -> Vec<String> {
+        let mut gavs: Vec<String> = Vec::new();
+        ivy.collect(&mut |_| true, &mut |gav: Gav| gavs.push(gav.to_string()));
+        gavs.sort();
+        gavs
+    }
+
+    fn sorted(paths: impl Iterator<Item = PathBuf>) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = paths.collect();
+        paths.sort();
+        paths
+    }
+}
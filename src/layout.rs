@@ -0,0 +1,464 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::{Display, Formatter};
+use std::fs::{read_to_string, remove_dir_all};
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+
+use anyhow::Result;
+use log::{debug, info};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use walkdir::WalkDir;
+
+use crate::backend::{measure_version, prune_version_dir, walk_versions, Backend};
+use crate::group::GroupFilter;
+use crate::origin::Origin;
+use crate::repo::{skipped_version_dir, version_dir, Gav};
+use crate::usage::DiskUsage;
+use crate::version::Version;
+
+// properties of the enhanced local repository manager of the maven resolver
+const SPLIT: &str = "aether.enhancedLocalRepository.split";
+const LOCAL_PREFIX: &str = "aether.enhancedLocalRepository.localPrefix";
+const REMOTE_PREFIX: &str = "aether.enhancedLocalRepository.remotePrefix";
+pub const DEFAULT_LOCAL_PREFIX: &str = "installed";
+pub const DEFAULT_REMOTE_PREFIX: &str = "cached";
+
+// ------------------------------------------------------ layout
+
+// The maven layout of a local repository: The directories which contain the
+// group directories. A plain repository has one root: the repository itself.
+// Maven 3.9+ can split the repository into 'installed/' and 'cached/' trees,
+// which may be further split by remote repository ID ('cached/central/') or
+// into releases and snapshots. Each of these directories is a root.
+#[derive(Debug, Clone)]
+pub struct Layout {
+    pub roots: Vec<Root>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Root {
+    pub path: PathBuf,
+    pub prefix: Option<Prefix>,
+    // the trees of a split repository, if the root is the repository itself
+    pub excludes: Vec<PathBuf>,
+}
+
+impl Root {
+    fn new(path: PathBuf, prefix: Option<Prefix>) -> Root {
+        Root {
+            path,
+            prefix,
+            excludes: Vec::new(),
+        }
+    }
+
+    fn excluded(&self, path: &Path) -> bool {
+        self.excludes
+            .iter()
+            .any(|exclude| path.starts_with(exclude))
+    }
+}
+
+impl Layout {
+    pub fn plain(path: &Path) -> Layout {
+        Layout {
+            roots: vec![Root::new(path.to_path_buf(), None)],
+        }
+    }
+
+    // Detects the layout using the properties 'aether.enhancedLocalRepository.*'
+    // or the directory structure. The roots inside the prefixes are derived from
+    // the group IDs in the poms. If that's not possible, the prefix is the root.
+    // Groups next to the prefixes (e.g. from before the split) stay in the
+    // repository itself, which is a root of its own then.
+    pub fn detect(path: &Path, properties: &HashMap<String, String>) -> Layout {
+        let property = |name: &str, default: &'static str| {
+            properties
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| default.to_string())
+        };
+        let local_prefix = property(LOCAL_PREFIX, DEFAULT_LOCAL_PREFIX);
+        let remote_prefix = property(REMOTE_PREFIX, DEFAULT_REMOTE_PREFIX);
+        let split = properties.get(SPLIT).is_some_and(|split| split == "true");
+        if !split {
+            if !path.join(&local_prefix).is_dir() && !path.join(&remote_prefix).is_dir() {
+                return Layout::plain(path);
+            }
+            info!(
+                "Split repository inferred from '{}' or '{}' in '{}'",
+                local_prefix,
+                remote_prefix,
+                path.display()
+            );
+        }
+
+        let mut roots = Vec::new();
+        let mut repository = Root::new(path.to_path_buf(), None);
+        for (prefix, name) in [
+            (Prefix::Installed, local_prefix),
+            (Prefix::Cached, remote_prefix),
+        ] {
+            let prefix_path = path.join(&name);
+            repository.excludes.push(PathBuf::from(name));
+            if !prefix_path.is_dir() {
+                continue;
+            }
+            let prefix_roots = find_roots(&prefix_path);
+            if prefix_roots.is_empty() {
+                roots.push(Root::new(prefix_path, Some(prefix)));
+            } else {
+                roots.extend(
+                    prefix_roots
+                        .into_iter()
+                        .map(|path| Root::new(path, Some(prefix))),
+                );
+            }
+        }
+        if has_versions(&repository) {
+            info!(
+                "Collect the groups next to the trees of the split repository '{}'",
+                path.display()
+            );
+            roots.push(repository);
+        }
+        Layout { roots }
+    }
+}
+
+impl Backend for Layout {
+    // The group ID is the path of the group relative to the root
+    fn collect(&self, walk_predicate: &mut dyn FnMut(&Path) -> bool, visit: &mut dyn FnMut(Gav)) {
+        for root in &self.roots {
+            walk_versions(
+                root.path.as_path(),
+                |path| !root.excluded(path) && walk_predicate(path),
+                |path| {
+                    let pom = version_dir(path);
+                    if pom.is_none() {
+                        if let Some(reason) = skipped_version_dir(path) {
+                            debug!("Skip '{}': {}", path.display(), reason);
+                        }
+                    }
+                    pom
+                },
+                &mut |version_path, pom| {
+                    let group_id = version_path
+                        .parent()
+                        .and_then(Path::parent)
+                        .and_then(|group_path| group_path.strip_prefix(&root.path).ok())
+                        .map(|group_path| {
+                            group_path
+                                .components()
+                                .map(|c| c.as_os_str().to_str().unwrap_or(""))
+                                .collect::<Vec<&str>>()
+                                .join(".")
+                        });
+                    if let Some(Ok(mut gav)) =
+                        group_id.map(|group_id| Gav::new(group_id.as_str(), version_path))
+                    {
+                        gav.version.pom = pom;
+                        gav.version.prefix = root.prefix;
+                        gav.version.origin = Origin::read(version_path);
+                        visit(gav);
+                    }
+                },
+            );
+        }
+    }
+
+    fn measure(&self, version: &mut Version, artifact_id: &str, disk_usage: &mut DiskUsage) {
+        measure_version(version, artifact_id, 1, &[], disk_usage);
+    }
+
+    fn remove(&self, version: &Version) -> Result<()> {
+        Ok(remove_dir_all(version.path.as_path())?)
+    }
+
+    // Versions of the same artifact may live in different roots of a split
+    // repository, so the artifact and group directories are taken from the version.
+    fn prune(&self, version: &Version) {
+        // TODO What if only maven metadata is left?
+        prune_version_dir(version.path.as_path());
+    }
+
+    fn version_dir(&self, path: &Path) -> bool {
+        version_dir(path).is_some()
+    }
+
+    fn group_paths(&self, group_id: &str) -> Vec<PathBuf> {
+        let group_path = group_id.replace('.', MAIN_SEPARATOR.to_string().as_str());
+        self.roots
+            .iter()
+            .map(|root| root.path.join(&group_path))
+            .collect()
+    }
+
+    fn group_scopes(&self, group_filter: &GroupFilter) -> Vec<PathBuf> {
+        self.roots
+            .iter()
+            .map(|root| root.path.join(group_filter.group_path.as_path()))
+            .collect()
+    }
+
+    fn split(&self) -> bool {
+        self.roots.iter().any(|root| root.prefix.is_some())
+    }
+}
+
+// Whether the root contains at least one version directory
+fn has_versions(root: &Root) -> bool {
+    WalkDir::new(&root.path)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|dir_entry| {
+            dir_entry
+                .path()
+                .strip_prefix(&root.path)
+                .map_or(true, |path| !root.excluded(path))
+        })
+        .flatten()
+        .any(|dir_entry| dir_entry.file_type().is_dir() && version_dir(dir_entry.path()).is_some())
+}
+
+// Finds the roots below 'dir' using the group IDs of the poms
+fn find_roots(dir: &Path) -> BTreeSet<PathBuf> {
+    let mut roots: BTreeSet<PathBuf> = BTreeSet::new();
+    let mut it = WalkDir::new(dir).min_depth(1).into_iter();
+    while let Some(entry) = it.next() {
+        let Ok(entry) = entry else { continue };
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+        let path = entry.path();
+        if roots.iter().any(|root| path.starts_with(root)) {
+            it.skip_current_dir();
+            continue;
+        }
+        if version_dir(path) == Some(true) {
+            if let Some(root) = repository_root(path) {
+                if root.starts_with(dir) {
+                    roots.insert(root);
+                }
+            }
+            it.skip_current_dir();
+        }
+    }
+    roots
+}
+
+// Returns the root of a version directory 'root/group/path/artifactId/version'
+// using the group ID of the pom.
+pub fn repository_root(version_path: &Path) -> Option<PathBuf> {
+    let pom = version_path
+        .read_dir()
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| path.extension().is_some_and(|extension| extension == "pom"))?;
+    let group_id = pom_group_id(read_to_string(pom).ok()?.as_str())?;
+
+    let mut group_path = version_path.parent()?.parent()?;
+    for segment in group_id.rsplit('.') {
+        if group_path.file_name()?.to_str()? != segment {
+            return None;
+        }
+        group_path = group_path.parent()?;
+    }
+    Some(group_path.to_path_buf())
+}
+
+// Returns '/project/groupId' or '/project/parent/groupId' of a pom
+pub fn pom_group_id(content: &str) -> Option<String> {
+    let mut reader = Reader::from_str(content);
+    reader.config_mut().trim_text(true);
+
+    let mut elements: Vec<String> = Vec::new();
+    let mut parent_group_id = None;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                elements.push(String::from_utf8_lossy(e.name().as_ref()).into_owned())
+            }
+            Ok(Event::End(_)) => {
+                elements.pop();
+            }
+            Ok(Event::Text(e)) => {
+                let path: Vec<&str> = elements.iter().map(String::as_str).collect();
+                match path.as_slice() {
+                    ["project", "groupId"] => return e.decode().ok().map(|t| t.into_owned()),
+                    ["project", "parent", "groupId"] => {
+                        parent_group_id = e.decode().ok().map(|t| t.into_owned())
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    parent_group_id
+}
+
+// ------------------------------------------------------ prefix
+
+// The trees of a split local repository
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Prefix {
+    // installed by 'mvn install'
+    Installed,
+    // downloaded from a remote repository
+    Cached,
+}
+
+impl Display for Prefix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Prefix::Installed => write!(f, "installed"),
+            Prefix::Cached => write!(f, "cached"),
+        }
+    }
+}
+
+// ------------------------------------------------------ split tests
+
+#[cfg(test)]
+mod split_tests {
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    use crate::backend::Backend;
+    use crate::layout::{pom_group_id, Layout, Prefix};
+    use crate::repo::skipped_version_dir;
+    use crate::temp::TempDir;
+
+    #[test]
+    fn group_id() {
+        assert_eq!(
+            Some("org.foo".to_string()),
+            pom_group_id(
+                "<project><groupId>org.foo</groupId><artifactId>bar</artifactId></project>"
+            )
+        );
+        assert_eq!(
+            Some("org.parent".to_string()),
+            pom_group_id(
+                "<project><parent><groupId>org.parent</groupId></parent><artifactId>bar</artifactId></project>"
+            )
+        );
+        assert_eq!(
+            Some("org.foo".to_string()),
+            pom_group_id(
+                "<project><parent><groupId>org.parent</groupId></parent><groupId>org.foo</groupId></project>"
+            )
+        );
+        assert_eq!(
+            None,
+            pom_group_id("<project><artifactId>bar</artifactId></project>")
+        );
+    }
+
+    #[test]
+    fn plain() {
+        let temp = TempDir::new("layout-plain");
+        let repository = temp.path.clone();
+        version(&temp, "", "org/foo", "org.foo");
+        let layout = Layout::detect(&repository, &HashMap::new());
+        assert!(!layout.split());
+        assert_eq!(vec![repository.clone()], roots(&layout));
+    }
+
+    #[test]
+    fn split() {
+        let temp = TempDir::new("layout-split");
+        let repository = temp.path.clone();
+        version(&temp, "installed", "org/foo", "org.foo");
+        version(&temp, "cached/central", "org/foo", "org.foo");
+        version(&temp, "cached/jboss", "org/jboss", "org.jboss");
+        let layout = Layout::detect(&repository, &HashMap::new());
+        assert!(layout.split());
+        assert_eq!(
+            vec![
+                repository.join("installed"),
+                repository.join("cached/central"),
+                repository.join("cached/jboss"),
+            ],
+            roots(&layout)
+        );
+        assert_eq!(Some(Prefix::Installed), layout.roots[0].prefix);
+        assert_eq!(Some(Prefix::Cached), layout.roots[1].prefix);
+    }
+
+    #[test]
+    fn mixed() {
+        let temp = TempDir::new("layout-mixed");
+        let repository = temp.path.clone();
+        version(&temp, "installed", "org/foo", "org.foo");
+        version(&temp, "", "org/bar", "org.bar");
+        let layout = Layout::detect(&repository, &HashMap::new());
+        assert_eq!(
+            vec![repository.join("installed"), repository.clone()],
+            roots(&layout)
+        );
+        assert_eq!(None, layout.roots[1].prefix);
+        let mut gavs: Vec<String> = Vec::new();
+        layout.collect(&mut |_| true, &mut |gav| gavs.push(gav.to_string()));
+        gavs.sort();
+        assert_eq!(vec!["org.bar:bar:1.0", "org.foo:bar:1.0"], gavs);
+    }
+
+    #[test]
+    fn custom_prefixes() {
+        let temp = TempDir::new("layout-custom");
+        let repository = temp.path.clone();
+        version(&temp, "local", "org/foo", "org.foo");
+        let properties = HashMap::from([
+            (
+                "aether.enhancedLocalRepository.split".to_string(),
+                "true".to_string(),
+            ),
+            (
+                "aether.enhancedLocalRepository.localPrefix".to_string(),
+                "local".to_string(),
+            ),
+        ]);
+        let layout = Layout::detect(&repository, &properties);
+        assert_eq!(vec![repository.join("local")], roots(&layout));
+    }
+
+    #[test]
+    fn skipped_version_dirs() {
+        let temp = TempDir::new("layout-skipped");
+        let repository = temp.path.clone();
+        version(&temp, "", "org/foo", "org.foo");
+        let artifact = repository.join("org/foo/bar");
+        for (dir, file) in [
+            ("latest", "bar-1.0.jar"),
+            ("2.0", "bar-2.0.jar.lastUpdated"),
+            ("3.0", "README"),
+        ] {
+            temp.file(artifact.join(dir).join(file), "");
+        }
+        assert_eq!(
+            Some("No files named 'bar-latest[-classifier].ext'".to_string()),
+            skipped_version_dir(&artifact.join("latest"))
+        );
+        assert_eq!(
+            Some("No files named 'bar-2.0[-classifier].ext'".to_string()),
+            skipped_version_dir(&artifact.join("2.0"))
+        );
+        assert_eq!(None, skipped_version_dir(&artifact.join("3.0")));
+        assert_eq!(None, skipped_version_dir(&artifact));
+    }
+
+    fn version(temp: &TempDir, root: &str, group_path: &str, group_id: &str) {
+        temp.file(
+            Path::new(root).join(group_path).join("bar/1.0/bar-1.0.pom"),
+            format!("<project><groupId>{}</groupId></project>", group_id),
+        );
+    }
+
+    fn roots(layout: &Layout) -> Vec<std::path::PathBuf> {
+        layout.roots.iter().map(|root| root.path.clone()).collect()
+    }
+}
@@ -0,0 +1,40 @@
+// mcup as a library: locate a repository, build a filter and run du, keep or
+// rm against it. The results are returned as data, nothing is printed.
+//
+//   use mcup::cleanup::Cleanup;
+//   use mcup::command::Command;
+//   use mcup::filter::Filter;
+//   use mcup::repo::{Location, Repository};
+//   use mcup::settings::MavenLocation;
+//
+//   let repository = Repository::locate(&Location::Maven(MavenLocation::default()))?;
+//   let report = Cleanup::new(repository, Command::Keep(true, false))
+//       .filter(Filter::builder().versions("1..").build()?)
+//       .run()?;
+//   println!("{} versions, {} bytes", report.repository.versions, report.repository.bytes);
+
+#[macro_use]
+extern crate lazy_static;
+
+pub mod artifact;
+pub mod backend;
+pub mod cleanup;
+pub mod command;
+pub mod discover;
+pub mod event;
+pub mod file;
+pub mod filter;
+pub mod gradle;
+pub mod group;
+pub mod ivy;
+pub mod layout;
+pub mod lock;
+pub mod origin;
+pub mod output;
+pub mod protect;
+pub mod repo;
+pub mod settings;
+#[cfg(test)]
+mod temp;
+pub mod usage;
+pub mod version;
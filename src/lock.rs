@@ -0,0 +1,165 @@
+use std::collections::BTreeSet;
+use std::fs::{File, OpenOptions, TryLockError};
+#[cfg(unix)]
+use std::mem;
+#[cfg(unix)]
+use std::os::fd::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use walkdir::WalkDir;
+
+use crate::gradle::is_module_cache;
+use crate::repo::Repository;
+
+// advisory lock of mcup in the root of the repository
+pub const LOCK_FILE: &str = ".mcup.lock";
+// named locks of the maven resolver (file-lock factory)
+const RESOLVER_LOCKS: &str = ".locks";
+// locks of in-flight downloads of the maven resolver
+const PART_LOCK_SUFFIX: &str = ".part.lock";
+// lock of the gradle module cache, held by builds and daemons using the cache
+const GRADLE_LOCK: &str = "modules-2.lock";
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// ------------------------------------------------------ repository lock
+
+// Advisory lock which prevents two instances of mcup from modifying the same
+// repository. The lock is released when dropped. The lock file is left in
+// place, since removing it would race with other processes opening it.
+#[derive(Debug)]
+pub struct RepositoryLock {
+    file: File,
+}
+
+impl RepositoryLock {
+    // Acquires the lock. Waits up to 'wait' if another process holds the lock.
+    pub fn acquire(repository: &Path, wait: Duration) -> Result<RepositoryLock> {
+        let path = repository.join(LOCK_FILE);
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path.as_path())
+            .with_context(|| format!("Unable to create lock file '{}'", path.display()))?;
+
+        let deadline = Instant::now() + wait;
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(RepositoryLock { file }),
+                Err(TryLockError::WouldBlock) => {
+                    if Instant::now() >= deadline {
+                        bail!(
+                            "The repository is locked by another instance of mcup: '{}'",
+                            path.display()
+                        )
+                    }
+                    sleep(POLL_INTERVAL);
+                }
+                Err(TryLockError::Error(error)) => {
+                    return Err(error)
+                        .with_context(|| format!("Unable to lock repository '{}'", path.display()))
+                }
+            }
+        }
+    }
+
+    // Waits up to 'wait' until no build uses the selected part of the repository.
+    pub fn wait_for_builds(&self, repository: &Repository, wait: Duration) -> Result<()> {
+        let deadline = Instant::now() + wait;
+        loop {
+            match build_activity(repository) {
+                Some(path) => {
+                    if Instant::now() >= deadline {
+                        bail!(
+                            "The repository is in use by a running build: '{}'",
+                            path.display()
+                        )
+                    }
+                    sleep(POLL_INTERVAL);
+                }
+                None => return Ok(()),
+            }
+        }
+    }
+}
+
+impl Drop for RepositoryLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+// Returns the first sign of a running build: A named lock of the maven
+// resolver or a lock of an in-flight download inside the selected artifacts,
+// which is held by another process. For the gradle module cache, it's the
+// lock of the module cache.
+fn build_activity(repository: &Repository) -> Option<PathBuf> {
+    if is_module_cache(&repository.path) {
+        let gradle_lock = repository.path.join(GRADLE_LOCK);
+        return locked(&gradle_lock).then_some(gradle_lock);
+    }
+    let resolver_locks = WalkDir::new(repository.path.join(RESOLVER_LOCKS))
+        .min_depth(1)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|dir_entry| dir_entry.file_type().is_file())
+        .map(|dir_entry| dir_entry.into_path());
+    let download_locks = repository
+        .groups
+        .values()
+        .flat_map(|group| group.artifacts.values())
+        .flat_map(|artifact| artifact.versions.values())
+        .filter_map(|version| version.path.parent())
+        .collect::<BTreeSet<&Path>>()
+        .into_iter()
+        .flat_map(|artifact_path| WalkDir::new(artifact_path).into_iter())
+        .filter_map(|e| e.ok())
+        .filter(|dir_entry| {
+            dir_entry.file_type().is_file()
+                && dir_entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| name.ends_with(PART_LOCK_SUFFIX))
+        })
+        .map(|dir_entry| dir_entry.into_path());
+    resolver_locks
+        .chain(download_locks)
+        .find(|path| locked(path))
+}
+
+// Whether another process holds a lock on the file. Lock files left behind
+// by killed builds are not locked anymore.
+//
+// Maven and Gradle lock files using 'FileChannel.lock()', which are record
+// locks (fcntl) on unix. They don't conflict with flock, so the lock is probed
+// with fcntl. Open file description locks see the record locks of this
+// process as well, other unix systems only see the locks of other processes.
+#[cfg(unix)]
+fn locked(path: &Path) -> bool {
+    #[cfg(target_os = "linux")]
+    const GET_LOCK: libc::c_int = libc::F_OFD_GETLK;
+    #[cfg(not(target_os = "linux"))]
+    const GET_LOCK: libc::c_int = libc::F_GETLK;
+
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+    // SAFETY: flock is a plain C struct, all zeros is a valid value
+    let mut lock: libc::flock = unsafe { mem::zeroed() };
+    // a write lock conflicts with any lock on the whole file
+    lock.l_type = libc::F_WRLCK as _;
+    lock.l_whence = libc::SEEK_SET as _;
+    // SAFETY: the file is open and the lock outlives the call
+    let result = unsafe { libc::fcntl(file.as_raw_fd(), GET_LOCK, &mut lock) };
+    result == 0 && lock.l_type != libc::F_UNLCK as _
+}
+
+// The JVM locks files using 'LockFileEx' on windows like the standard library.
+#[cfg(not(unix))]
+fn locked(path: &Path) -> bool {
+    File::open(path)
+        .is_ok_and(|file| matches!(file.try_lock_shared(), Err(TryLockError::WouldBlock)))
+}
@@ -0,0 +1,44 @@
+use console::style;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+// ------------------------------------------------------ logger
+
+// Prints the log messages of mcup to stderr. Warnings and errors are always
+// printed, '--verbose' adds debug and '--verbose --verbose' trace messages.
+pub struct Logger;
+
+static LOGGER: Logger = Logger;
+
+impl Logger {
+    pub fn init(verbose: u8) {
+        let level = match verbose {
+            0 => LevelFilter::Warn,
+            1 => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        };
+        if log::set_logger(&LOGGER).is_ok() {
+            log::set_max_level(level);
+        }
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level() && metadata.target().starts_with("mcup")
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            let level = match record.level() {
+                Level::Error => style("error:").red().bold(),
+                Level::Warn => style("warning:").yellow().bold(),
+                Level::Info => style("info:").green().bold(),
+                Level::Debug => style("debug:").cyan(),
+                Level::Trace => style("trace:").dim(),
+            };
+            eprintln!("{} {}", level, record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
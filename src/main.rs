@@ -1,86 +1,137 @@
-#[macro_use]
-extern crate lazy_static;
-
 mod app;
-mod artifact;
-mod command;
-mod filter;
-mod group;
-mod repo;
-mod version;
+mod cli;
+mod confirm;
+mod inspect;
+mod logger;
+mod progress;
 
 use anyhow::{bail, Result};
 use clap::ArgMatches;
-use glob::Pattern;
+use console::style;
+use mcup::cleanup::{Cleanup, Report};
+use mcup::command::Command;
+use mcup::command::Command::Du;
+use mcup::discover::discover;
+use mcup::filter::Filter;
+use mcup::output;
+use mcup::repo::Repository;
 use std::io::{stdout, IsTerminal};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 
 use app::build_app;
 
-use crate::command::Command;
-use crate::filter::Filter;
-use crate::repo::Repository;
-use crate::version::VersionRange;
-use console::style;
+use crate::cli::{parse_timeout, validate_command, wait_timeout};
+use crate::confirm::Confirmation;
+use crate::logger::Logger;
+use crate::progress::Progress;
 
 fn main() -> Result<()> {
     let args = build_app()
-        .mut_arg("artifacts", |arg| arg.value_parser(parse_artifacts))
-        .mut_arg("versions", |arg| arg.value_parser(parse_versions))
+        .mut_subcommand("keep", |cmd| {
+            cmd.mut_arg("wait", |arg| arg.value_parser(parse_timeout))
+        })
+        .mut_subcommand("rm", |cmd| {
+            cmd.mut_arg("wait", |arg| arg.value_parser(parse_timeout))
+        })
         .get_matches();
     validate_command(&args)?;
+    Logger::init(args.get_count("verbose"));
 
-    let mut local_repo = Repository::locate(&args)?;
-    if local_repo.exists() {
-        let command = Command::from(&args);
-        let filter = Filter::from(&args, local_repo.path.as_path());
-        let duration = local_repo.process(&command, &filter);
-        if stdout().is_terminal() {
-            println!();
-            command.summary(&local_repo, duration);
+    match args.subcommand() {
+        Some(("versions", sub_args)) => return inspect::versions(&args, sub_args),
+        Some(("version-compare", sub_args)) => return inspect::compare(sub_args),
+        _ => {}
+    }
+    let command = cli::command(&args);
+    match args.get_one::<String>("discover") {
+        Some(root) => {
+            let paths = discover(Path::new(root))?;
+            if paths.is_empty() {
+                bail!("No local maven repositories found in '{}'", root)
+            }
+            let mut repositories = Vec::new();
+            let mut duration = Duration::ZERO;
+            for path in paths {
+                let filter = cli::filter(&args)?;
+                let local_repo = Repository::open(path);
+                // du prints the path in the header of the summary
+                if summary(&args, &command) && !matches!(command, Du(_)) {
+                    println!();
+                    println!("{}", style(local_repo.path.display()).bold());
+                }
+                if let Some(report) = run(&args, &command, filter, local_repo)? {
+                    duration += report.duration;
+                    repositories.push(report.repository);
+                }
+            }
+            if summary(&args, &command) {
+                println!();
+                output::total(&mut stdout(), &command, &repositories, duration)?;
+            }
+            Ok(())
+        }
+        None => {
+            let filter = cli::filter(&args)?;
+            let local_repo = Repository::locate(&cli::location(&args))?;
+            if local_repo.exists() {
+                run(&args, &command, filter, local_repo)?;
+                Ok(())
+            } else if args.get_flag("gradle") {
+                bail!(
+                    "Gradle module cache does not exist: '{}'",
+                    local_repo.path.display()
+                )
+            } else if args.get_flag("ivy") {
+                bail!("Ivy home does not exist: '{}'", local_repo.path.display())
+            } else {
+                bail!(
+                    "Local maven repository does not exist: '{}'",
+                    local_repo.path.display()
+                )
+            }
         }
-        Ok(())
-    } else {
-        bail!(
-            "Local maven repository does not exist: '{}'",
-            local_repo.path.display()
-        )
     }
 }
 
-// ------------------------------------------------------ validation
-
-fn validate_command(args: &ArgMatches) -> Result<()> {
-    if (args.subcommand_matches("keep").is_some() || args.subcommand_matches("rm").is_some())
-        && !args.contains_id("groups")
-        && !args.contains_id("artifacts")
-        && !args.contains_id("versions")
-        && !args.get_flag("snapshots")
-        && !args.get_flag("releases")
-    {
-        bail!(
-            r#"Subcommand {} requires a filter, but one was not provided
-
-USAGE:
-    mcup [FLAGS] [OPTIONS] <SUBCOMMAND>
-
-For more information try {}"#,
-            style(format!("'{}'", args.subcommand_name().unwrap())).yellow(),
-            style("--help").green()
-        )
+// Runs the command against one repository. Returns None if the removal has not been confirmed.
+fn run(
+    args: &ArgMatches,
+    command: &Command,
+    filter: Filter,
+    local_repo: Repository,
+) -> Result<Option<Report>> {
+    let progress = Progress::new(
+        command,
+        filter.classifier_filter.is_some(),
+        !args.get_flag("quiet") && args.get_count("verbose") == 0,
+    );
+    let selection = Cleanup::new(local_repo, command.clone())
+        .filter(filter)
+        .wait(wait_timeout(args))
+        .observer(Arc::new(progress))
+        .select()?;
+    let confirmation = Confirmation::from(args);
+    if !confirmation.confirm(&selection.repository, command, &selection.filter)? {
+        return Ok(None);
     }
-    Ok(())
-}
-
-fn parse_artifacts(artifacts: &str) -> Result<Pattern, String> {
-    match Pattern::new(artifacts) {
-        Ok(p) => Ok(p),
-        Err(e) => Err(format!("Illegal artifact pattern: {}", e.msg)),
+    let report = selection.remove()?;
+    if summary(args, command) {
+        println!();
+        output::summary(
+            &mut stdout(),
+            command,
+            &report.repository,
+            &report.filter,
+            report.duration,
+        )?;
     }
+    Ok(Some(report))
 }
 
-fn parse_versions(version: &str) -> Result<VersionRange, String> {
-    match VersionRange::parse(version) {
-        Ok(v) => Ok(v),
-        Err(e) => Err(e.to_string()),
-    }
+// Summaries are printed for humans only. '--quiet' skips the summaries of
+// keep and rm, but not the summary of du, which is the result.
+fn summary(args: &ArgMatches, command: &Command) -> bool {
+    stdout().is_terminal() && (matches!(command, Du(_)) || !args.get_flag("quiet"))
 }
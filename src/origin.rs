@@ -0,0 +1,228 @@
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use std::fs::{read_dir, read_to_string};
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use glob::Pattern;
+
+use crate::version::Version;
+
+// written by the maven resolver into each version directory
+pub const REMOTE_REPOSITORIES: &str = "_remote.repositories";
+// snapshot metadata is stored as 'maven-metadata-<repoId>.xml'
+const METADATA_PREFIX: &str = "maven-metadata-";
+const METADATA_SUFFIX: &str = ".xml";
+
+// ------------------------------------------------------ origin
+
+// Where the files of a version come from according to '_remote.repositories'.
+// The file contains lines like 'foo-1.0.jar>central=' for downloaded files and
+// 'foo-1.0.jar>=' for files installed by 'mvn install'. The names of the
+// snapshot metadata files ('maven-metadata-central.xml') are taken into account
+// as well. Versions without any of them are considered to be local, since
+// there's no proof of a remote origin. Versions in the module cache of gradle
+// are downloaded from an unknown remote repository: neither local nor any ID.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Origin {
+    pub local: bool,
+    pub repositories: BTreeSet<String>,
+}
+
+impl Origin {
+    pub fn read(version_path: &Path) -> Origin {
+        let mut origin = match read_to_string(version_path.join(REMOTE_REPOSITORIES)) {
+            Ok(content) => Origin::parse(content.as_str()),
+            Err(_) => Origin::default(),
+        };
+        if let Ok(entries) = read_dir(version_path) {
+            for entry in entries.flatten() {
+                let file_name = entry.file_name();
+                match metadata_repository(file_name.to_string_lossy().as_ref()) {
+                    Some("local") => origin.local = true,
+                    Some(repository) => {
+                        origin.repositories.insert(repository.to_string());
+                    }
+                    None => {}
+                }
+            }
+        }
+        origin.local = origin.local || origin.repositories.is_empty();
+        origin
+    }
+
+    pub fn parse(content: &str) -> Origin {
+        let mut local = false;
+        let mut repositories = BTreeSet::new();
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some((_, repository)) = line.split_once('>') {
+                let repository = repository.trim_end_matches('=');
+                if repository.is_empty() {
+                    local = true;
+                } else {
+                    repositories.insert(repository.to_string());
+                }
+            }
+        }
+        Origin {
+            local: local || repositories.is_empty(),
+            repositories,
+        }
+    }
+}
+
+// Returns the repository ID of metadata files like 'maven-metadata-central.xml'
+pub fn metadata_repository(file_name: &str) -> Option<&str> {
+    file_name
+        .strip_prefix(METADATA_PREFIX)
+        .and_then(|name| name.strip_suffix(METADATA_SUFFIX))
+        .filter(|repository| !repository.is_empty())
+}
+
+impl Display for Origin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.local && self.repositories.is_empty() {
+            return write!(f, "remote");
+        }
+        let mut origins: Vec<&str> = Vec::new();
+        if self.local {
+            origins.push("local");
+        }
+        origins.extend(self.repositories.iter().map(String::as_str));
+        write!(f, "{}", origins.join(", "))
+    }
+}
+
+// ------------------------------------------------------ origin filter
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum OriginFilter {
+    Local,
+    Remote,
+    Repository(String),
+}
+
+impl OriginFilter {
+    pub fn parse(origin: &str) -> OriginFilter {
+        match origin {
+            "local" => OriginFilter::Local,
+            "remote" => OriginFilter::Remote,
+            repository => OriginFilter::Repository(repository.to_string()),
+        }
+    }
+
+    pub fn match_origin(&self, version: &Version) -> bool {
+        match self {
+            OriginFilter::Local => version.origin.local,
+            OriginFilter::Remote => {
+                !version.origin.local || !version.origin.repositories.is_empty()
+            }
+            OriginFilter::Repository(repository) => {
+                version.origin.repositories.contains(repository)
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------ repository filter
+
+#[derive(Debug, Clone)]
+pub struct RepositoryFilter {
+    pub repositories: Vec<Pattern>,
+}
+
+impl RepositoryFilter {
+    // Parses a comma separated list of patterns like 'central,jboss-*'
+    pub fn parse(repository_ids: &str) -> Result<RepositoryFilter> {
+        let repositories = repository_ids
+            .split(',')
+            .map(|repository_id| match Pattern::new(repository_id.trim()) {
+                Ok(p) => Ok(p),
+                Err(e) => Err(anyhow!(
+                    "Illegal repository pattern '{}': {}",
+                    repository_id,
+                    e.msg
+                )),
+            })
+            .collect::<Result<Vec<Pattern>>>()?;
+        Ok(RepositoryFilter { repositories })
+    }
+
+    // whether the version has been downloaded from one of the repositories
+    pub fn match_repository(&self, version: &Version) -> bool {
+        version.origin.repositories.iter().any(|repository| {
+            self.repositories
+                .iter()
+                .any(|pattern| pattern.matches(repository))
+        })
+    }
+}
+
+// ------------------------------------------------------ origin tests
+
+#[cfg(test)]
+mod origin_tests {
+    use crate::origin::{metadata_repository, Origin};
+
+    #[test]
+    fn downloaded() {
+        let origin = Origin::parse(
+            r#"#NOTE: This is a Maven Resolver internal implementation file, its format can be changed without prior notice.
+#Mon Jan 01 12:00:00 CET 2024
+foo-1.0.jar>central=
+foo-1.0.pom>central=
+"#,
+        );
+        assert!(!origin.local);
+        assert_eq!(
+            vec!["central"],
+            origin.repositories.iter().collect::<Vec<_>>()
+        );
+        assert_eq!("central", origin.to_string());
+    }
+
+    #[test]
+    fn installed() {
+        let origin = Origin::parse("foo-1.0.jar>=\nfoo-1.0.pom>=\n");
+        assert!(origin.local);
+        assert!(origin.repositories.is_empty());
+        assert_eq!("local", origin.to_string());
+    }
+
+    #[test]
+    fn mixed() {
+        let origin =
+            Origin::parse("foo-1.0.jar>=\nfoo-1.0.pom>jboss=\nfoo-1.0-sources.jar>central=");
+        assert!(origin.local);
+        assert_eq!("local, central, jboss", origin.to_string());
+    }
+
+    #[test]
+    fn empty() {
+        assert!(Origin::parse("").local);
+        assert!(Origin::parse("# comment only").local);
+    }
+
+    #[test]
+    fn unknown_remote() {
+        assert_eq!("remote", Origin::default().to_string());
+    }
+
+    #[test]
+    fn metadata() {
+        assert_eq!(
+            Some("central"),
+            metadata_repository("maven-metadata-central.xml")
+        );
+        assert_eq!(
+            Some("jboss-ga"),
+            metadata_repository("maven-metadata-jboss-ga.xml")
+        );
+        assert_eq!(None, metadata_repository("maven-metadata.xml"));
+        assert_eq!(None, metadata_repository("maven-metadata-central.xml.sha1"));
+        assert_eq!(None, metadata_repository("foo-1.0.pom"));
+    }
+}
@@ -0,0 +1,941 @@
+use std::cmp::{Ordering, Reverse};
+use std::io;
+use std::io::Write;
+use std::time::Duration;
+
+use bit_vec::BitVec;
+use console::{pad_str, Alignment, Style};
+use indicatif::{DecimalBytes, HumanDuration};
+
+use crate::artifact::Artifact;
+use crate::command::Command::{Du, Keep, Remove};
+use crate::command::{Command, DuOptions};
+use crate::file::ArtifactFile;
+use crate::filter::Filter;
+use crate::group::Group;
+use crate::repo::Repository;
+use crate::version::Version;
+
+// ------------------------------------------------------ summary
+
+// Renders the summary of du, keep and rm like the command line does. The
+// styles follow the color settings of stdout.
+
+pub fn summary(
+    out: &mut dyn Write,
+    command: &Command,
+    repository: &Repository,
+    filter: &Filter,
+    duration: Duration,
+) -> io::Result<()> {
+    match command {
+        Keep(dry_run, _) | Remove(dry_run, _) => rm_summary(
+            out,
+            repository,
+            duration,
+            *dry_run,
+            filter.classifier_filter.is_some(),
+        ),
+        Du(options) => du_summary(out, repository, options),
+    }
+}
+
+// grand total of several repositories (--discover)
+pub fn total(
+    out: &mut dyn Write,
+    command: &Command,
+    repositories: &[Repository],
+    duration: Duration,
+) -> io::Result<()> {
+    match command {
+        Keep(dry_run, _) | Remove(dry_run, _) => rm_total(out, repositories, duration, *dry_run),
+        Du(options) => du_total(out, repositories, options),
+    }
+}
+
+// ------------------------------------------------------ common styles
+
+struct Styles {
+    dim: Style,
+    normal: Style,
+    bold: Style,
+    origins: Style,
+    groups: Style,
+    artifacts: Style,
+    versions: Style,
+    files: Style,
+    summary: Style,
+    dry_run: Style,
+    protected: Style,
+}
+
+lazy_static! {
+    static ref STYLES: Styles = Styles {
+        dim: Style::new().dim(),
+        normal: Style::new().for_stdout(),
+        bold: Style::new().magenta().bold(),
+        origins: Style::new().red(),
+        groups: Style::new().cyan(),
+        artifacts: Style::new().green(),
+        versions: Style::new().yellow(),
+        files: Style::new().blue(),
+        summary: Style::new().green(),
+        dry_run: Style::new().yellow(),
+        protected: Style::new().cyan(),
+    };
+}
+
+// ------------------------------------------------------ rm / keep
+
+fn rm_summary(
+    out: &mut dyn Write,
+    repository: &Repository,
+    duration: Duration,
+    dry_run: bool,
+    files: bool,
+) -> io::Result<()> {
+    let bytes = DecimalBytes(repository.bytes);
+    let duration = HumanDuration(duration);
+
+    writeln!(
+        out,
+        "The operation {}",
+        if dry_run {
+            STYLES.dry_run.apply_to("would affect ")
+        } else {
+            STYLES.normal.apply_to("affects")
+        }
+    )?;
+    writeln!(out)?;
+    affected(out, repository, files)?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "The operation took {} and {}released {}.",
+        STYLES.summary.apply_to(duration),
+        if dry_run {
+            STYLES.dry_run.apply_to("would have ")
+        } else {
+            STYLES.normal.apply_to("")
+        },
+        STYLES.summary.apply_to(bytes)
+    )?;
+    protected(out, repository)?;
+    if dry_run {
+        writeln!(
+            out,
+            "Since you've used {} no artifacts have been removed.",
+            STYLES.dry_run.apply_to("--dry-run")
+        )?;
+    }
+    Ok(())
+}
+
+fn rm_total(
+    out: &mut dyn Write,
+    repositories: &[Repository],
+    duration: Duration,
+    dry_run: bool,
+) -> io::Result<()> {
+    let sum = |count: fn(&Repository) -> usize| repositories.iter().map(count).sum::<usize>();
+    writeln!(
+        out,
+        "In total the operation {} {} repositories with",
+        if dry_run {
+            STYLES.dry_run.apply_to("would have affected")
+        } else {
+            STYLES.normal.apply_to("affected")
+        },
+        STYLES.summary.apply_to(repositories.len())
+    )?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "    {} groups,",
+        STYLES.summary.apply_to(sum(|r| r.groups.len()))
+    )?;
+    writeln!(
+        out,
+        "    {} artifacts and",
+        STYLES.summary.apply_to(sum(|r| r.artifacts))
+    )?;
+    writeln!(
+        out,
+        "    {} versions",
+        STYLES.summary.apply_to(sum(|r| r.versions))
+    )?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "The operations took {} and {}released {}.",
+        STYLES.summary.apply_to(HumanDuration(duration)),
+        if dry_run {
+            STYLES.dry_run.apply_to("would have ")
+        } else {
+            STYLES.normal.apply_to("")
+        },
+        STYLES
+            .summary
+            .apply_to(DecimalBytes(repositories.iter().map(|r| r.bytes).sum()))
+    )
+}
+
+pub fn protected(out: &mut dyn Write, repository: &Repository) -> io::Result<()> {
+    if repository.protected > 0 {
+        writeln!(
+            out,
+            "{} protected versions have been skipped.",
+            STYLES.protected.apply_to(repository.protected)
+        )?;
+    }
+    Ok(())
+}
+
+pub fn affected(out: &mut dyn Write, repository: &Repository, files: bool) -> io::Result<()> {
+    writeln!(
+        out,
+        "    {} groups,",
+        STYLES.summary.apply_to(repository.groups.len())
+    )?;
+    writeln!(
+        out,
+        "    {} artifacts and",
+        STYLES.summary.apply_to(repository.artifacts)
+    )?;
+    if files {
+        writeln!(
+            out,
+            "    {} files in {} versions",
+            STYLES.summary.apply_to(repository.files),
+            STYLES.summary.apply_to(repository.versions)
+        )?;
+    } else {
+        writeln!(
+            out,
+            "    {} versions",
+            STYLES.summary.apply_to(repository.versions)
+        )?;
+    }
+    Ok(())
+}
+
+// ------------------------------------------------------ du
+
+const SIZE_COLUMN: usize = 10;
+const TEXT_COLUMN: usize = 64;
+const GROUPS_COLUMN: usize = 13;
+const ARTIFACTS_COLUMN: usize = 16;
+const VERSIONS_COLUMN: usize = 33;
+const COUNT_WIDTH: usize = 4;
+const UNACCOUNTED: &str = "(unaccounted)";
+const NO_POM: &str = "no pom";
+const OPAQUE: &str = "opaque";
+
+fn du_summary(out: &mut dyn Write, repository: &Repository, options: &DuOptions) -> io::Result<()> {
+    let mut bits = BitVec::new();
+    bits.push(options.origins);
+    bits.push(options.groups);
+    bits.push(options.artifacts);
+    bits.push(options.versions);
+    bits.push(options.files);
+    let hierarchy = bits.iter().filter(|b| *b).count() > 1;
+
+    let empty = repository.is_empty() && unaccounted(repository, options) == 0;
+    header(
+        out,
+        options.size(repository.bytes, repository.apparent_bytes),
+        repository.path.display().to_string().as_str(),
+    )?;
+    if !empty {
+        post_header(out)?;
+    }
+    body(out, repository, options, hierarchy)?;
+    if !empty {
+        footer_of(out, repository, options, ["┢", "╈", "┪"])?;
+    } else {
+        footer_of(out, repository, options, ["┣", "╋", "┫"])?;
+    }
+    Ok(())
+}
+
+// one row per repository plus the grand total in the footer
+fn du_total(
+    out: &mut dyn Write,
+    repositories: &[Repository],
+    options: &DuOptions,
+) -> io::Result<()> {
+    let size = |repository: &Repository| options.size(repository.bytes, repository.apparent_bytes);
+    let total = repositories.iter().map(size).sum();
+    header(
+        out,
+        total,
+        format!("{} repositories", repositories.len()).as_str(),
+    )?;
+    post_header(out)?;
+    let mut sorted_repositories: Vec<&Repository> = repositories.iter().collect();
+    sorted_repositories.sort_by_key(|r| Reverse(size(r)));
+    for repository in sorted_repositories {
+        size_and_text(
+            out,
+            size(repository),
+            repository.path.display().to_string().as_str(),
+            &STYLES.normal,
+        )?;
+    }
+    footer(
+        out,
+        total,
+        repositories.iter().map(|r| r.groups.len()).sum(),
+        repositories.iter().map(|r| r.artifacts).sum(),
+        repositories.iter().map(|r| r.versions).sum(),
+        ["┢", "╈", "┪"],
+    )
+}
+
+// ------------------------------------------------------ versions
+
+// Lists the versions of an artifact in the order used by version ranges: '1'
+// is the latest version, which is kept by '--versions 1..'. 'versions' must
+// be sorted from highest to lowest.
+pub fn versions(
+    out: &mut dyn Write,
+    group_id: &str,
+    artifact_id: &str,
+    versions: &[&Version],
+) -> io::Result<()> {
+    let total = versions.iter().map(|version| version.bytes).sum();
+    header(out, total, format!("{group_id}:{artifact_id}").as_str())?;
+    post_header(out)?;
+    dim(out, "│")?;
+    write!(out, "{}", " ".repeat(SIZE_COLUMN))?;
+    dim(out, "│")?;
+    text_pad(
+        out,
+        version_columns(
+            "#",
+            "version",
+            "major",
+            "minor",
+            "patch",
+            "qualifier",
+            "type",
+        )
+        .as_str(),
+        TEXT_COLUMN,
+        &STYLES.dim,
+    )?;
+    dim(out, "│")?;
+    writeln!(out)?;
+    for (index, version) in versions.iter().enumerate() {
+        let number = |n: Option<u32>| n.map_or(String::from("-"), |n| n.to_string());
+        let version_type = if version.is_opaque() {
+            OPAQUE
+        } else if version.snapshot {
+            "snapshot"
+        } else {
+            "release"
+        };
+        size_and_text(
+            out,
+            version.bytes,
+            version_columns(
+                (index + 1).to_string().as_str(),
+                version.name.as_str(),
+                number(version.major).as_str(),
+                number(version.minor).as_str(),
+                number(version.patch).as_str(),
+                version.qualifier.as_deref().unwrap_or("-"),
+                version_type,
+            )
+            .as_str(),
+            &STYLES.versions,
+        )?;
+    }
+    footer(out, total, 1, 1, versions.len(), ["┢", "╈", "┪"])
+}
+
+// Shows how two versions are parsed and which one is greater. 'a' and 'b'
+// are the versions as given by the user.
+pub fn version_compare(
+    out: &mut dyn Write,
+    a: (&str, &Version),
+    b: (&str, &Version),
+) -> io::Result<()> {
+    let width = a.0.len().max(b.0.len());
+    for (input, version) in [a, b] {
+        writeln!(
+            out,
+            "{}  {}",
+            STYLES
+                .versions
+                .apply_to(pad_str(input, width, Alignment::Left, None)),
+            STYLES.dim.apply_to(version_components(version))
+        )?;
+    }
+    writeln!(out)?;
+    let ordering = match a.1.cmp(b.1) {
+        Ordering::Less => "<",
+        Ordering::Equal => "=",
+        Ordering::Greater => ">",
+    };
+    writeln!(
+        out,
+        "{} {} {}",
+        STYLES.versions.apply_to(a.0),
+        STYLES.bold.apply_to(ordering),
+        STYLES.versions.apply_to(b.0)
+    )
+}
+
+// '#', version, major, minor, patch, qualifier and type padded to TEXT_COLUMN
+fn version_columns(
+    index: &str,
+    version: &str,
+    major: &str,
+    minor: &str,
+    patch: &str,
+    qualifier: &str,
+    version_type: &str,
+) -> String {
+    format!(
+        "{:>3}  {:<18} {:>5} {:>5} {:>5}  {:<10} {}",
+        index,
+        pad_str(version, 18, Alignment::Left, Some("...")),
+        major,
+        minor,
+        patch,
+        pad_str(qualifier, 10, Alignment::Left, Some("...")),
+        version_type
+    )
+}
+
+fn version_components(version: &Version) -> String {
+    if version.is_opaque() {
+        return format!(
+            "opaque '{}'{}",
+            version.qualifier.as_deref().unwrap_or(""),
+            if version.snapshot { ", snapshot" } else { "" }
+        );
+    }
+    let number = |n: Option<u32>| n.map_or(String::from("-"), |n| n.to_string());
+    format!(
+        "major {}, minor {}, patch {}, qualifier {}{}",
+        number(version.major),
+        number(version.minor),
+        number(version.patch),
+        version
+            .qualifier
+            .as_ref()
+            .map_or(String::from("-"), |qualifier| format!("'{qualifier}'")),
+        if version.snapshot { ", snapshot" } else { "" }
+    )
+}
+
+// ------------------------------------------------------ header, body, footer
+
+fn header(out: &mut dyn Write, size: u64, text: &str) -> io::Result<()> {
+    dim(out, "┏")?;
+    line(out, "━", SIZE_COLUMN)?;
+    dim(out, "┳")?;
+    line(out, "━", TEXT_COLUMN)?;
+    dim(out, "┓")?;
+    writeln!(out)?;
+
+    dim(out, "┃")?;
+    size_pad(out, size, &STYLES.bold)?;
+    dim(out, "┃")?;
+    text_pad(out, text, TEXT_COLUMN, &STYLES.bold)?;
+    dim(out, "┃")?;
+    writeln!(out)
+}
+
+fn post_header(out: &mut dyn Write) -> io::Result<()> {
+    dim(out, "┡")?;
+    line(out, "━", SIZE_COLUMN)?;
+    dim(out, "╇")?;
+    line(out, "━", TEXT_COLUMN)?;
+    dim(out, "┩")?;
+    writeln!(out)
+}
+
+fn body(
+    out: &mut dyn Write,
+    repository: &Repository,
+    options: &DuOptions,
+    hierarchy: bool,
+) -> io::Result<()> {
+    let levels = [
+        options.origins,
+        options.groups,
+        options.artifacts,
+        options.versions,
+        options.files,
+    ];
+
+    if options.origins {
+        let origins = repository.origins();
+        let mut sorted_origins: Vec<(&String, &Repository)> = origins.iter().collect();
+        sorted_origins.sort_by_key(|(_, o)| Reverse(options.size(o.bytes, o.apparent_bytes)));
+        for (origin_index, (origin, origin_repository)) in sorted_origins.iter().enumerate() {
+            let last_origin = origin_index == sorted_origins.len() - 1;
+            let origin_path = [(origin.as_str(), last_origin)];
+            row(
+                out,
+                &levels,
+                &origin_path,
+                "",
+                options.size(origin_repository.bytes, origin_repository.apparent_bytes),
+                &STYLES.origins,
+            )?;
+            groups(
+                out,
+                origin_repository,
+                options,
+                hierarchy,
+                &levels,
+                origin_path[0],
+            )?;
+            if !last_origin
+                && (options.groups || options.artifacts || options.versions || options.files)
+            {
+                separator(out)?;
+            }
+        }
+    } else {
+        groups(out, repository, options, hierarchy, &levels, ("", true))?;
+    }
+
+    // files which are not part of any group
+    let repository_unaccounted = unaccounted(repository, options);
+    if repository_unaccounted > 0 {
+        if !repository.is_empty()
+            && (options.groups && options.origins
+                || options.artifacts
+                || options.versions
+                || options.files)
+        {
+            separator(out)?;
+        }
+        size_and_text(out, repository_unaccounted, UNACCOUNTED, &STYLES.dim)?;
+    }
+    Ok(())
+}
+
+// Prints the groups of the repository. If the usage is grouped by origin,
+// 'origin' is the name of the origin and whether it's the last one.
+fn groups(
+    out: &mut dyn Write,
+    repository: &Repository,
+    options: &DuOptions,
+    hierarchy: bool,
+    levels: &[bool; 5],
+    origin: (&str, bool),
+) -> io::Result<()> {
+    let mut sorted_groups: Vec<&Group> = repository.groups.values().collect();
+    sorted_groups.sort_by_key(|g| Reverse(options.size(g.bytes, g.apparent_bytes)));
+    for (group_index, group) in sorted_groups.iter().enumerate() {
+        let last_group = group_index == sorted_groups.len() - 1;
+        let group_path = [origin, (group.id.as_str(), last_group)];
+        row(
+            out,
+            levels,
+            &group_path,
+            "",
+            options.size(group.bytes, group.apparent_bytes),
+            &STYLES.groups,
+        )?;
+
+        // files of the group which are not part of a version
+        let group_unaccounted =
+            options.size(group.unaccounted_bytes, group.unaccounted_apparent_bytes);
+        let show_group_unaccounted =
+            hierarchy && options.groups && !options.origins && group_unaccounted > 0;
+
+        // artifacts
+        let mut sorted_artifacts: Vec<&Artifact> = group.artifacts.values().collect();
+        sorted_artifacts.sort_by_key(|a| Reverse(options.size(a.bytes, a.apparent_bytes)));
+        for (artifact_index, artifact) in sorted_artifacts.iter().enumerate() {
+            let last_artifact =
+                artifact_index == sorted_artifacts.len() - 1 && !show_group_unaccounted;
+            let artifact_path = [origin, group_path[1], (artifact.id.as_str(), last_artifact)];
+            row(
+                out,
+                levels,
+                &artifact_path,
+                "",
+                options.size(artifact.bytes, artifact.apparent_bytes),
+                &STYLES.artifacts,
+            )?;
+
+            // versions
+            let mut sorted_versions: Vec<&Version> = artifact.versions.values().collect();
+            sorted_versions.reverse();
+            for (version_index, version) in sorted_versions.iter().enumerate() {
+                let last_version = version_index == sorted_versions.len() - 1;
+                let version_name = version.to_string();
+                let version_note = version_note(version);
+                let version_path = [
+                    origin,
+                    group_path[1],
+                    artifact_path[2],
+                    (version_name.as_str(), last_version),
+                ];
+                row(
+                    out,
+                    levels,
+                    &version_path,
+                    version_note.as_str(),
+                    options.size(version.bytes, version.apparent_bytes),
+                    &STYLES.versions,
+                )?;
+
+                // files
+                if options.files {
+                    let mut sorted_files: Vec<&ArtifactFile> = version.files.iter().collect();
+                    sorted_files.sort_by_key(|f| Reverse(options.size(f.bytes, f.apparent_bytes)));
+                    for (file_index, file) in sorted_files.iter().enumerate() {
+                        let last_file = file_index == sorted_files.len() - 1;
+                        let file_path = [
+                            origin,
+                            group_path[1],
+                            artifact_path[2],
+                            version_path[3],
+                            (file.name.as_str(), last_file),
+                        ];
+                        row(
+                            out,
+                            levels,
+                            &file_path,
+                            "",
+                            options.size(file.bytes, file.apparent_bytes),
+                            &STYLES.files,
+                        )?;
+                    }
+                }
+            }
+        }
+        if show_group_unaccounted {
+            size_and_text_n(out, group_unaccounted, UNACCOUNTED, &STYLES.dim, &[true])?;
+        }
+        if !last_group
+            && !options.origins
+            && (options.artifacts || options.versions || options.files)
+        {
+            separator(out)?;
+        }
+    }
+    Ok(())
+}
+
+// origin, opaque version and missing pom in parentheses like ' (local, no pom)'
+fn version_note(version: &Version) -> String {
+    let mut notes = vec![version.origin.to_string()];
+    if version.is_opaque() {
+        notes.push(OPAQUE.to_string());
+    }
+    if !version.pom {
+        notes.push(NO_POM.to_string());
+    }
+    format!(" ({})", notes.join(", "))
+}
+
+// Prints a row if its level (origin, group, artifact, version or file) is part
+// of the output. 'path' contains the names from the origin down to the row and
+// whether they're the last entry of their parent. Levels which are not part
+// of the output are folded into the name and the tree of the next level.
+fn row(
+    out: &mut dyn Write,
+    levels: &[bool; 5],
+    path: &[(&str, bool)],
+    note: &str,
+    size: u64,
+    style: &Style,
+) -> io::Result<()> {
+    let level = path.len() - 1;
+    if !levels[level] {
+        return Ok(());
+    }
+
+    let first = levels.iter().position(|l| *l).unwrap_or(level);
+    let parent = (0..level).rev().find(|l| levels[*l]);
+    let mut name = path[parent.map_or(0, |p| p + 1)..]
+        .iter()
+        .map(|(name, _)| *name)
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<&str>>()
+        .join(":");
+    name.push_str(note);
+
+    if level == first {
+        size_and_text(out, size, name.as_str(), style)?;
+    } else {
+        let mut lasts: Vec<bool> = Vec::new();
+        let mut last = true;
+        for (index, (_, last_entry)) in path.iter().enumerate().skip(first + 1) {
+            last = last && *last_entry;
+            if levels[index] {
+                lasts.push(last);
+                last = true;
+            }
+        }
+        size_and_text_n(out, size, name.as_str(), style, &lasts)?;
+    }
+    Ok(())
+}
+
+// Returns the size of the files which are not part of any group. If groups are
+// not part of the output or the usage is grouped by origin, the unaccounted files
+// of the groups are added as well.
+fn unaccounted(repository: &Repository, options: &DuOptions) -> u64 {
+    let mut bytes = options.size(
+        repository.unaccounted_bytes,
+        repository.unaccounted_apparent_bytes,
+    );
+    if !options.groups || options.origins {
+        bytes += repository
+            .groups
+            .values()
+            .map(|group| options.size(group.unaccounted_bytes, group.unaccounted_apparent_bytes))
+            .sum::<u64>();
+    }
+    bytes
+}
+
+fn footer_of(
+    out: &mut dyn Write,
+    repository: &Repository,
+    options: &DuOptions,
+    edges: [&'static str; 3],
+) -> io::Result<()> {
+    footer(
+        out,
+        options.size(repository.bytes, repository.apparent_bytes),
+        repository.groups.len(),
+        repository.artifacts,
+        repository.versions,
+        edges,
+    )
+}
+
+fn footer(
+    out: &mut dyn Write,
+    size: u64,
+    groups: usize,
+    artifacts: usize,
+    versions: usize,
+    edges: [&'static str; 3],
+) -> io::Result<()> {
+    let [vertical_left, cross, right_vertical] = edges;
+    let groups = format!("{}", groups);
+    let artifacts = format!("{}", artifacts);
+    let versions = format!("{}", versions);
+
+    dim(out, vertical_left)?;
+    line(out, "━", SIZE_COLUMN)?;
+    dim(out, cross)?;
+    line(out, "━", GROUPS_COLUMN)?;
+    dim(out, "┳")?;
+    line(out, "━", ARTIFACTS_COLUMN)?;
+    dim(out, "┳")?;
+    line(out, "━", VERSIONS_COLUMN)?;
+    dim(out, right_vertical)?;
+    writeln!(out)?;
+
+    dim(out, "┃")?;
+    size_pad(out, size, &STYLES.bold)?;
+    dim(out, "┃")?;
+    writeln!(
+        out,
+        " {} {} {} {} {} {} {} {} {}",
+        STYLES.bold.apply_to(pad_str(
+            groups.as_str(),
+            COUNT_WIDTH,
+            Alignment::Right,
+            None,
+        )),
+        STYLES.groups.apply_to("groups"),
+        STYLES.dim.apply_to("┃"),
+        STYLES.bold.apply_to(pad_str(
+            artifacts.as_str(),
+            COUNT_WIDTH,
+            Alignment::Right,
+            None,
+        )),
+        STYLES.artifacts.apply_to("artifacts"),
+        STYLES.dim.apply_to("┃"),
+        STYLES.bold.apply_to(pad_str(
+            versions.as_str(),
+            COUNT_WIDTH,
+            Alignment::Right,
+            None,
+        )),
+        STYLES.versions.apply_to(pad_str(
+            "versions",
+            VERSIONS_COLUMN - COUNT_WIDTH - 3,
+            Alignment::Left,
+            None,
+        )),
+        STYLES.dim.apply_to("┃"),
+    )?;
+
+    dim(out, "┗")?;
+    line(out, "━", SIZE_COLUMN)?;
+    dim(out, "┻")?;
+    line(out, "━", GROUPS_COLUMN)?;
+    dim(out, "┻")?;
+    line(out, "━", ARTIFACTS_COLUMN)?;
+    dim(out, "┻")?;
+    line(out, "━", VERSIONS_COLUMN)?;
+    dim(out, "┛")?;
+    writeln!(out)
+}
+
+// ------------------------------------------------------ helper functions
+
+fn size_and_text(out: &mut dyn Write, size: u64, text: &str, style: &Style) -> io::Result<()> {
+    dim(out, "│")?;
+    size_pad(out, size, style)?;
+    dim(out, "│")?;
+    text_pad(out, text, TEXT_COLUMN, style)?;
+    dim(out, "│")?;
+    writeln!(out)
+}
+
+// 'lasts' contains whether the row and its parents are the last entry of their parent
+fn size_and_text_n(
+    out: &mut dyn Write,
+    size: u64,
+    text: &str,
+    style: &Style,
+    lasts: &[bool],
+) -> io::Result<()> {
+    dim(out, "│")?;
+    size_pad(out, size, style)?;
+    dim(out, "│")?;
+    let mut indent = String::from(" ");
+    for (index, last) in lasts.iter().enumerate() {
+        if index == lasts.len() - 1 {
+            indent.push_str(if *last { "└──" } else { "├──" });
+        } else {
+            indent.push_str(if *last { "    " } else { "│   " });
+        }
+    }
+    dim(out, indent.as_str())?;
+    text_pad(out, text, TEXT_COLUMN - 4 * lasts.len(), style)?;
+    dim(out, "│")?;
+    writeln!(out)
+}
+
+fn size_pad(out: &mut dyn Write, size: u64, style: &Style) -> io::Result<()> {
+    let size = DecimalBytes(size).to_string();
+    write!(
+        out,
+        " {} ",
+        style.apply_to(pad_str(
+            size.as_str(),
+            SIZE_COLUMN - 2,
+            Alignment::Right,
+            None,
+        )),
+    )
+}
+
+fn text_pad(out: &mut dyn Write, text: &str, length: usize, style: &Style) -> io::Result<()> {
+    write!(
+        out,
+        " {} ",
+        style.apply_to(pad_str(text, length - 2, Alignment::Left, Some("...")))
+    )
+}
+
+fn separator(out: &mut dyn Write) -> io::Result<()> {
+    dim(out, "├")?;
+    line(out, "─", SIZE_COLUMN)?;
+    dim(out, "┼")?;
+    line(out, "─", TEXT_COLUMN)?;
+    dim(out, "┤")?;
+    writeln!(out)
+}
+
+fn line(out: &mut dyn Write, symbol: &str, length: usize) -> io::Result<()> {
+    for _ in 0..length {
+        dim(out, symbol)?;
+    }
+    Ok(())
+}
+
+#[inline]
+fn dim(out: &mut dyn Write, text: &str) -> io::Result<()> {
+    write!(out, "{}", STYLES.dim.apply_to(text))
+}
+
+// ------------------------------------------------------ output tests
+
+#[cfg(test)]
+mod output_tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use crate::command::Command::{Du, Remove};
+    use crate::command::DuOptions;
+    use crate::filter::Filter;
+    use crate::output::summary;
+    use crate::repo::Repository;
+    use crate::version::Version;
+
+    #[test]
+    fn rm() {
+        let repository = Repository::open(PathBuf::from("repository"));
+        let mut out = Vec::new();
+        summary(
+            &mut out,
+            &Remove(true, false),
+            &repository,
+            &Filter::default(),
+            Duration::ZERO,
+        )
+        .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("The operation would affect"));
+        assert!(out.contains("    0 versions\n"));
+        assert!(out.ends_with("Since you've used --dry-run no artifacts have been removed.\n"));
+    }
+
+    #[test]
+    fn du() {
+        let repository = Repository::open(PathBuf::from("repository"));
+        let mut out = Vec::new();
+        summary(
+            &mut out,
+            &Du(DuOptions::default()),
+            &repository,
+            &Filter::default(),
+            Duration::ZERO,
+        )
+        .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(5, lines.len());
+        assert!(lines[1].contains("repository"));
+        assert!(lines[3].contains("0 groups"));
+    }
+
+    #[test]
+    fn version_compare() {
+        console::set_colors_enabled(false);
+        let mut out = Vec::new();
+        super::version_compare(
+            &mut out,
+            ("1.0-Beta", &Version::parse("1.0-Beta").unwrap()),
+            ("latest", &Version::opaque("latest")),
+        )
+        .unwrap();
+        assert_eq!(
+            "1.0-Beta  major 1, minor 0, patch -, qualifier 'Beta'\n\
+             latest    opaque 'latest'\n\
+             \n\
+             1.0-Beta > latest\n",
+            String::from_utf8(out).unwrap()
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use indicatif::{ProgressBar, ProgressStyle};
+use log::warn;
+use mcup::command::Command;
+use mcup::command::Command::{Keep, Remove};
+use mcup::event::{Event, Observer};
+
+const PROGRESS_BAR_THRESHOLD: usize = 5;
+
+// ------------------------------------------------------ progress
+
+// Shows a spinner while the repository is scanned and a progress bar while
+// versions or files are removed. '--list' prints the removed paths instead
+// of the progress bar. '--quiet' and '--verbose' turn off both, the latter
+// to not mix them up with the log messages.
+#[derive(Debug)]
+pub struct Progress {
+    show: bool,
+    dry_run: bool,
+    list: bool,
+    // whether files are removed instead of versions (--classifiers)
+    files: bool,
+    progress_bar: Mutex<Option<ProgressBar>>,
+}
+
+impl Progress {
+    pub fn new(command: &Command, files: bool, show: bool) -> Progress {
+        let (dry_run, list) = match command {
+            Keep(dry_run, list) | Remove(dry_run, list) => (*dry_run, *list),
+            _ => (false, false),
+        };
+        Progress {
+            show,
+            dry_run,
+            list,
+            files,
+            progress_bar: Mutex::new(None),
+        }
+    }
+
+    fn start(&self, progress_bar: ProgressBar) {
+        if let Ok(mut current) = self.progress_bar.lock() {
+            *current = Some(progress_bar);
+        }
+    }
+
+    fn with<F: FnOnce(&ProgressBar)>(&self, f: F) {
+        if let Ok(current) = self.progress_bar.lock() {
+            if let Some(progress_bar) = current.as_ref() {
+                f(progress_bar);
+            }
+        }
+    }
+
+    fn finish(&self) {
+        if let Ok(mut current) = self.progress_bar.lock() {
+            if let Some(progress_bar) = current.take() {
+                progress_bar.finish_and_clear();
+            }
+        }
+    }
+}
+
+impl Observer for Progress {
+    fn notify(&self, event: &Event) {
+        match event {
+            Event::ScanStart if self.show => {
+                let progress_bar = ProgressBar::new_spinner()
+                    .with_prefix("Check artifacts")
+                    .with_style(
+                        ProgressStyle::default_spinner()
+                            .tick_chars("/|\\- ")
+                            .template("{spinner:.dim.bold} {prefix} {wide_msg}")
+                            .unwrap(),
+                    );
+                progress_bar.enable_steady_tick(Duration::from_millis(100));
+                self.start(progress_bar);
+            }
+            Event::Scan(gav) => self.with(|progress_bar| progress_bar.set_message(gav.to_string())),
+            Event::ScanEnd | Event::RemovalEnd => self.finish(),
+            Event::ScanStart | Event::Select { .. } => {}
+            Event::RemovalStart(count) => {
+                if self.show && !self.dry_run && !self.list && *count > PROGRESS_BAR_THRESHOLD {
+                    self.start(
+                        ProgressBar::new(*count as u64)
+                            .with_prefix(if self.files {
+                                "Remove files"
+                            } else {
+                                "Remove artifacts"
+                            })
+                            .with_style(
+                                ProgressStyle::default_bar()
+                                    .progress_chars("#>-")
+                                    .template(
+                                        "{prefix} [{wide_bar:.green/yellow}] {pos:>6}/{len:6}",
+                                    )
+                                    .unwrap(),
+                            ),
+                    );
+                }
+            }
+            Event::Remove(paths) => {
+                if self.list {
+                    for path in paths.iter() {
+                        if let Some(str) = path.as_os_str().to_str() {
+                            println!("{str}");
+                        }
+                    }
+                } else {
+                    self.with(|progress_bar| progress_bar.inc(1));
+                }
+            }
+            Event::Error(path, error) => {
+                let message = format!("Unable to remove '{}': {}", path.display(), error);
+                match self
+                    .progress_bar
+                    .lock()
+                    .ok()
+                    .and_then(|current| current.clone())
+                {
+                    Some(progress_bar) => progress_bar.suspend(|| warn!("{message}")),
+                    None => warn!("{message}"),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use directories::BaseDirs;
+use glob::Pattern;
+
+use crate::version::Version;
+
+// protection file in the root of the local repository
+const REPOSITORY_PROTECT_FILE: &str = ".mcup-protect";
+// protection file in the user config directory (e.g. ~/.config/mcup/protect)
+const USER_PROTECT_FILE: &str = "mcup/protect";
+
+// ------------------------------------------------------ protection
+
+// Coordinates which are never removed by keep or rm. Protection files contain
+// one pattern per line: '<groupId>[:<artifactId>[:<version>]]'. Each part
+// supports globbing. Groups include their subgroups. Lines starting with '#'
+// are comments.
+pub struct Protection {
+    pub patterns: Vec<CoordinatePattern>,
+}
+
+impl Protection {
+    // Reads the protection files of the repository and of the user.
+    // Returns None if there are no protected coordinates.
+    pub fn load(local_repo: &Path) -> Result<Option<Protection>> {
+        let mut files = vec![local_repo.join(REPOSITORY_PROTECT_FILE)];
+        if let Some(base_dirs) = BaseDirs::new() {
+            files.push(base_dirs.config_dir().join(USER_PROTECT_FILE));
+        }
+
+        let mut patterns = Vec::new();
+        for file in files.iter().filter(|file| file.exists()) {
+            patterns.extend(Protection::read(file)?);
+        }
+        if patterns.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(Protection { patterns }))
+        }
+    }
+
+    fn read(file: &PathBuf) -> Result<Vec<CoordinatePattern>> {
+        let content =
+            read_to_string(file).with_context(|| format!("Unable to read '{}'", file.display()))?;
+        content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                CoordinatePattern::parse(line)
+                    .with_context(|| format!("Invalid protection in '{}'", file.display()))
+            })
+            .collect()
+    }
+
+    pub fn protects(&self, group_id: &str, artifact_id: &str, version: &Version) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern| pattern.matches(group_id, artifact_id, version.name.as_str()))
+    }
+}
+
+// ------------------------------------------------------ coordinate pattern
+
+pub struct CoordinatePattern {
+    pub group: Pattern,
+    pub artifact: Option<Pattern>,
+    pub version: Option<Pattern>,
+}
+
+impl CoordinatePattern {
+    pub fn parse(coordinates: &str) -> Result<CoordinatePattern> {
+        let parts: Vec<&str> = coordinates.split(':').map(str::trim).collect();
+        if parts.len() > 3 || parts.iter().any(|part| part.is_empty()) {
+            bail!(
+                "Illegal coordinates: '{}'. Use <groupId>[:<artifactId>[:<version>]].",
+                coordinates
+            )
+        }
+        let pattern =
+            |part: &str| Pattern::new(part).with_context(|| format!("Illegal pattern: '{part}'"));
+        Ok(CoordinatePattern {
+            group: pattern(parts[0])?,
+            artifact: parts.get(1).map(|part| pattern(part)).transpose()?,
+            version: parts.get(2).map(|part| pattern(part)).transpose()?,
+        })
+    }
+
+    pub fn matches(&self, group_id: &str, artifact_id: &str, version: &str) -> bool {
+        self.match_group(group_id)
+            && self
+                .artifact
+                .as_ref()
+                .is_none_or(|artifact| artifact.matches(artifact_id))
+            && self
+                .version
+                .as_ref()
+                .is_none_or(|pattern| pattern.matches(version))
+    }
+
+    // a group matches if the group itself or one of its parent groups matches
+    fn match_group(&self, group_id: &str) -> bool {
+        group_id
+            .match_indices('.')
+            .map(|(index, _)| &group_id[..index])
+            .chain(std::iter::once(group_id))
+            .any(|group| self.group.matches(group))
+    }
+}
+
+// ------------------------------------------------------ protection tests
+
+#[cfg(test)]
+mod protection_tests {
+    use crate::protect::CoordinatePattern;
+
+    #[test]
+    fn invalid_coordinates() {
+        assert!(CoordinatePattern::parse("").is_err());
+        assert!(CoordinatePattern::parse(":foo").is_err());
+        assert!(CoordinatePattern::parse("org::1.0").is_err());
+        assert!(CoordinatePattern::parse("org:foo:1.0:jar").is_err());
+        assert!(CoordinatePattern::parse("org.[").is_err());
+    }
+
+    #[test]
+    fn groups() {
+        let pattern = CoordinatePattern::parse("org.acme").unwrap();
+        assert!(pattern.matches("org.acme", "foo", "1.0"));
+        assert!(pattern.matches("org.acme.core", "foo", "1.0"));
+        assert!(!pattern.matches("org.acmex", "foo", "1.0"));
+        assert!(!pattern.matches("org", "foo", "1.0"));
+    }
+
+    #[test]
+    fn artifacts_and_versions() {
+        let pattern = CoordinatePattern::parse("org.acme:legacy-*:1.*").unwrap();
+        assert!(pattern.matches("org.acme", "legacy-core", "1.2.3"));
+        assert!(!pattern.matches("org.acme", "legacy-core", "2.0"));
+        assert!(!pattern.matches("org.acme", "modern-core", "1.2.3"));
+    }
+}
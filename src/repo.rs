@@ -1,104 +1,100 @@
-use std::collections::{BTreeMap, HashSet};
-use std::ffi::OsStr;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::{Display, Formatter};
-use std::fs::{read_to_string, remove_dir, remove_dir_all};
+use std::fs::remove_file;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
-use clap::ArgMatches;
-use directories::BaseDirs;
-use indicatif::{ProgressBar, ProgressStyle};
-use quick_xml::events::Event;
-use quick_xml::name::QName;
-use quick_xml::Reader;
-use walkdir::{DirEntry, WalkDir};
+use log::{debug, trace};
+use regex::Regex;
+use walkdir::WalkDir;
 
 use crate::artifact::Artifact;
+use crate::backend::{detect, Backend};
 use crate::command::Command;
 use crate::command::Command::{Du, Keep, Remove};
+use crate::event::{Event, Observer, Silent};
+use crate::file::ClassifierFilter;
 use crate::filter::Filter;
+use crate::gradle::module_cache;
 use crate::group::Group;
+use crate::ivy::ivy_home;
+use crate::lock::LOCK_FILE;
+use crate::protect::Protection;
+use crate::settings::{local_repository, MavenLocation};
+use crate::usage::DiskUsage;
+use crate::version::VersionRange::{Latest, LatestPer, Oldest};
 use crate::version::{Version, VersionRange};
 
-const PROGRESS_BAR_THRESHOLD: usize = 5;
+lazy_static! {
+    // <yyyyMMdd>.<HHmmss>-<build number> of timestamped snapshots
+    static ref SNAPSHOT_TIMESTAMP: Regex = Regex::new(r"^\d{8}\.\d{6}-\d+").unwrap();
+}
 
 // ------------------------------------------------------ repo
 
+// Where to look for the repository (see Repository::locate())
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Location {
+    Maven(MavenLocation),
+    Gradle,
+    Ivy,
+}
+
 #[derive(Debug)]
 pub struct Repository {
     pub path: PathBuf,
+    pub backend: Arc<dyn Backend>,
     pub groups: BTreeMap<String, Group>,
     pub artifacts: usize,
     pub versions: usize,
+    pub files: usize,
+    // selected versions which have been skipped due to a protection
+    pub protected: usize,
     pub bytes: u64,
+    pub apparent_bytes: u64,
+    // files not part of any group (du --all only)
+    pub unaccounted_bytes: u64,
+    pub unaccounted_apparent_bytes: u64,
+    // gets notified about the progress of select() and remove()
+    pub observer: Arc<dyn Observer>,
 }
 
 impl Repository {
-    // Locate the local repository in that order:
-    //   1. The value of the option `--local-repository`
-    //   2. The value of `<localRepository/>` in `~/.m2/settings.xml`
-    //   3. Fall back to `~/.m2/repository/`
+    // Locates the local repository like maven does (see settings::local_repository()),
+    // the module cache of gradle or '~/.ivy2'.
     // Whether the local repository really exists is the caller's responsibility
-    pub fn locate(args: &ArgMatches) -> Result<Repository> {
-        match args.get_one::<String>("local-repository") {
-            Some(path) => {
-                // use --local-repository option
-                Ok(Repository::new(Path::new(path).to_path_buf()))
-            }
-            None => {
-                // try ~/.m2/settings.xml
-                let base_dirs = BaseDirs::new().with_context(|| "No home directory")?;
-                let home = base_dirs.home_dir();
-                let settings_xml = home.join(".m2/settings.xml");
-                if settings_xml.exists() {
-                    let string = read_to_string(settings_xml)
-                        .with_context(|| "Unable to read ~/.settings.xml")?;
-                    let mut reader = Reader::from_str(string.as_str());
-                    reader.config_mut().trim_text(true);
-
-                    let local_repo = loop {
-                        match reader.read_event() {
-                            Ok(Event::Start(ref e)) => {
-                                if e.name().as_ref() == b"localRepository" {
-                                    break reader
-                                        .read_text(QName(b"localRepository"))
-                                        .ok()
-                                        .and_then(|t| t.decode().ok())
-                                        .map(|s| s.into_owned());
-                                }
-                            }
-                            Ok(Event::Eof) => break None,
-                            Err(_) => break None,
-                            _ => {}
-                        };
-                    };
+    pub fn locate(location: &Location) -> Result<Repository> {
+        let (path, properties) = match location {
+            Location::Maven(maven_location) => local_repository(maven_location)?,
+            Location::Gradle => (module_cache()?, HashMap::new()),
+            Location::Ivy => (ivy_home()?, HashMap::new()),
+        };
+        let backend = detect(&path, &properties);
+        Ok(Repository::new(path, backend))
+    }
 
-                    match local_repo {
-                        Some(value) => {
-                            // use <localRepository/> value from ~/.m2/settings.xml
-                            Ok(Repository::new(Path::new(&value).to_path_buf()))
-                        }
-                        None => {
-                            // no <localRepository/> tag, fall back to ~/.m2/repository
-                            Ok(Repository::new(base_dirs.home_dir().join(".m2/repository")))
-                        }
-                    }
-                } else {
-                    // no ~/.m2/settings.xml, fall back to ~/.m2/repository
-                    Ok(Repository::new(base_dirs.home_dir().join(".m2/repository")))
-                }
-            }
-        }
+    // Opens the repository at 'path' and detects its backend
+    pub fn open(path: PathBuf) -> Repository {
+        let backend = detect(&path, &HashMap::new());
+        Repository::new(path, backend)
     }
 
-    fn new(path: PathBuf) -> Repository {
+    pub fn new(path: PathBuf, backend: Arc<dyn Backend>) -> Repository {
         Repository {
             path,
+            backend,
             groups: BTreeMap::new(),
             artifacts: 0,
             versions: 0,
+            files: 0,
+            protected: 0,
             bytes: 0,
+            apparent_bytes: 0,
+            unaccounted_bytes: 0,
+            unaccounted_apparent_bytes: 0,
+            observer: Arc::new(Silent),
         }
     }
 
@@ -110,7 +106,41 @@ impl Repository {
         self.groups.is_empty()
     }
 
-    pub fn process(&mut self, command: &Command, filter: &Filter) -> Duration {
+    // Splits the selected versions by their origin (du -o o). Versions downloaded
+    // from several repositories end up in a combined origin like 'central, jboss',
+    // so that the origins add up to the size of the repository.
+    pub fn origins(&self) -> BTreeMap<String, Repository> {
+        let mut origins: BTreeMap<String, Repository> = BTreeMap::new();
+        for group in self.groups.values() {
+            for artifact in group.artifacts.values() {
+                for version in artifact.versions.values() {
+                    let origin = origins
+                        .entry(version.origin.to_string())
+                        .or_insert_with(|| {
+                            Repository::new(self.path.clone(), self.backend.clone())
+                        });
+                    origin
+                        .groups
+                        .entry(group.id.clone())
+                        .or_insert_with(|| Group::new(group.id.as_str(), group.path.as_path()))
+                        .artifacts
+                        .entry(artifact.id.clone())
+                        .or_insert_with(|| {
+                            Artifact::new(artifact.id.as_str(), artifact.path.as_path())
+                        })
+                        .add_version(version.clone());
+                }
+            }
+        }
+        for origin in origins.values_mut() {
+            origin.compute();
+        }
+        origins
+    }
+
+    // Collects and measures the artifacts selected by the command and filter.
+    // For keep and rm, the repository contains the artifacts to remove afterwards.
+    pub fn select(&mut self, command: &Command, filter: &Filter) -> Duration {
         let now = Instant::now();
 
         // collect GAVs
@@ -118,18 +148,28 @@ impl Repository {
             if filter.artifact_filter.is_none()
                 && filter.version_range.is_none()
                 && filter.release_type.is_none()
+                && filter.origin_filter.is_none()
+                && filter.repository_filter.is_none()
+                && filter.prefix.is_none()
             {
                 // groups only
                 match command {
                     Keep(_, _) => {
-                        // Remove everything that is not part of the specified (sub)groups
-                        self.collect(|dir_entry| group_filter.no_subgroup_of(dir_entry), |_| true)
+                        // Remove everything that is not part of the specified (sub)groups.
+                        // The conjunction is left with the opaque versions only.
+                        self.collect(
+                            |path| group_filter.no_subgroup_of(path),
+                            |gav| filter.conjunction(gav, command),
+                        )
                     }
-                    Remove(_, _) | Du(_, _, _) => {
+                    Remove(_, _) | Du(_) => {
                         // remove or analyze specified (sub)groups
                         self.collect(
-                            |dir_entry| group_filter.subgroup_of(dir_entry),
-                            |gav| group_filter.match_group_id(gav.group.id.as_str()),
+                            |path| group_filter.subgroup_of(path),
+                            |gav| {
+                                group_filter.match_group_id(gav.group.id.as_str())
+                                    && filter.conjunction(gav, command)
+                            },
                         )
                     }
                 }
@@ -137,7 +177,7 @@ impl Repository {
                 // scope is the specified group,
                 // then apply the specified filters
                 self.collect(
-                    |dir_entry| group_filter.subgroup_of(dir_entry),
+                    |path| group_filter.subgroup_of(path),
                     |gav| {
                         group_filter.match_group_id(gav.group.id.as_str())
                             && filter.conjunction(gav, command)
@@ -153,96 +193,113 @@ impl Repository {
         // add GAVs to repo
         self.add_all(gavs);
 
-        // filter version ranges
-        if let Some(ref version_range) = filter.version_range {
+        // filter count based version ranges (the others are part of the conjunction)
+        if let Some(ref version_range @ (Latest(_) | Oldest(_) | LatestPer(_, _))) =
+            filter.version_range
+        {
             self.remove_version_range(version_range, command);
         }
 
+        // skip protected versions
+        if let (Keep(_, _) | Remove(_, _), Some(protection)) = (command, &filter.protection) {
+            self.remove_protected(protection);
+        }
+
+        // measure versions and files
+        let mut disk_usage = DiskUsage::new();
+        self.measure(&mut disk_usage);
+
+        // filter files by classifier
+        if let Some(ref classifier_filter) = filter.classifier_filter {
+            self.select_files(classifier_filter, command);
+        }
+
         // sum up bytes & counters
         self.compute();
+        for group in self.groups.values() {
+            for artifact in group.artifacts.values() {
+                for version in artifact.versions.values() {
+                    trace!("Select {}:{}:{}", group.id, artifact.id, version);
+                    self.observer.notify(&Event::Select {
+                        group_id: group.id.as_str(),
+                        artifact_id: artifact.id.as_str(),
+                        version,
+                    });
+                }
+            }
+        }
+        if let Du(options) = command {
+            if options.all {
+                let scopes = match filter.group_filter {
+                    Some(ref group_filter) => self.backend.group_scopes(group_filter),
+                    None => vec![self.path.clone()],
+                };
+                self.compute_unaccounted(&scopes, &mut disk_usage);
+            }
+        }
+
+        // done
+        now.elapsed()
+    }
+
+    // Removes the selected versions or files (keep and rm only). Returns the
+    // removed paths or the paths which would be removed by a dry run.
+    pub fn remove(&self, command: &Command, filter: &Filter) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = Vec::new();
 
-        // remove versions
-        if let Keep(dry_run, list) | Remove(dry_run, list) = command {
-            self.remove_versions(*dry_run, *list);
+        // remove versions or files
+        if let Keep(dry_run, _) | Remove(dry_run, _) = command {
+            if filter.classifier_filter.is_some() {
+                self.remove_files(*dry_run, &mut paths);
+            } else {
+                self.remove_versions(*dry_run, &mut paths);
+            }
             if !(*dry_run) {
                 self.prune_empty_directories();
             }
         }
 
         // done
-        now.elapsed()
+        paths
+    }
+
+    // Returns the disk usage of the whole repository
+    pub fn disk_usage(&self) -> u64 {
+        let mut disk_usage = DiskUsage::new();
+        WalkDir::new(self.path.as_path())
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|dir_entry| !dir_entry.file_type().is_symlink())
+            .filter_map(|dir_entry| dir_entry.metadata().ok())
+            .map(|meta| disk_usage.file(&meta).0)
+            .sum()
     }
 
-    fn collect<P, Q>(&mut self, walk_predicate: P, gav_predicate: Q) -> Vec<Gav>
+    // Walks the repository using the backend. The walk predicate gets the path
+    // of groups, artifacts and versions relative to the roots (see Backend::collect()).
+    fn collect<P, Q>(&mut self, mut walk_predicate: P, gav_predicate: Q) -> Vec<Gav>
     where
-        P: FnMut(&DirEntry) -> bool,
+        P: FnMut(&Path) -> bool,
         Q: Fn(&Gav) -> bool,
     {
         let mut gavs: Vec<Gav> = Vec::new();
-        let progress_bar = ProgressBar::new_spinner()
-            .with_prefix("Check artifacts")
-            .with_style(
-                ProgressStyle::default_spinner()
-                    .tick_chars("/|\\- ")
-                    .template("{spinner:.dim.bold} {prefix} {wide_msg}")
-                    .unwrap(),
-            );
-        progress_bar.enable_steady_tick(Duration::from_millis(100));
-
-        for dir_entry in WalkDir::new(self.path.as_path())
-            .min_depth(1)
-            .into_iter()
-            .filter_entry(walk_predicate)
-            .filter_map(|e| e.ok())
-        {
-            if dir_entry.path().is_file()
-                && dir_entry
-                    .path()
-                    .extension()
-                    .unwrap_or_else(|| OsStr::new(""))
-                    == "pom"
-            {
-                if let Ok(gav) = self.gav(dir_entry.path()) {
-                    progress_bar.set_message(gav.to_string());
-                    if gav_predicate(&gav) {
-                        gavs.push(gav);
-                    }
-                }
+        self.observer.notify(&Event::ScanStart);
+        let observer = self.observer.clone();
+        self.backend.collect(&mut walk_predicate, &mut |gav| {
+            observer.notify(&Event::Scan(&gav));
+            if gav.version.is_opaque() {
+                debug!("Opaque version {gav}");
             }
-        }
-
-        progress_bar.finish_and_clear();
+            if gav_predicate(&gav) {
+                gavs.push(gav);
+            } else {
+                trace!("Skip {gav}: excluded by the filters");
+            }
+        });
+        self.observer.notify(&Event::ScanEnd);
         gavs
     }
 
-    fn gav(&mut self, pom: &Path) -> Result<Gav> {
-        let version_path = pom.parent().with_context(|| "No version path")?;
-        let version = Version::from_path(version_path)?;
-
-        let artifact_path = version_path.parent().with_context(|| "No artifact path")?;
-        let artifact_id = artifact_path
-            .file_name()
-            .with_context(|| "No artifact ID")?
-            .to_str()
-            .with_context(|| "No artifact ID")?;
-        let artifact = Artifact::new(artifact_id, artifact_path);
-
-        let group_path = artifact_path.parent().with_context(|| "No group path")?;
-        let group_id: String = group_path
-            .strip_prefix(self.path.as_path())?
-            .components()
-            .map(|c| c.as_os_str().to_str().unwrap_or(""))
-            .collect::<Vec<&str>>()
-            .join(".");
-        let group = Group::new(group_id.as_str(), group_path);
-
-        Ok(Gav {
-            group,
-            artifact,
-            version,
-        })
-    }
-
     fn add_all(&mut self, gavs: Vec<Gav>) {
         for gav in gavs {
             let group = self.groups.entry(gav.group.id.clone()).or_insert(gav.group);
@@ -250,27 +307,30 @@ impl Repository {
                 .artifacts
                 .entry(gav.artifact.id.clone())
                 .or_insert(gav.artifact);
-            artifact
-                .versions
-                .entry(gav.version.clone())
-                .or_insert(gav.version);
+            artifact.add_version(gav.version);
         }
     }
 
     fn remove_version_range(&mut self, version_range: &VersionRange, command: &Command) {
         for group in self.groups.values_mut() {
             for artifact in group.artifacts.values_mut() {
-                let mut artifact_versions: Vec<Version> =
-                    artifact.versions.keys().cloned().collect();
+                // Opaque versions have no place in the order of the version
+                // range. They're selected by '--opaque' only.
+                let mut artifact_versions: Vec<Version> = artifact
+                    .versions
+                    .keys()
+                    .map(|(version, _)| version)
+                    .filter(|version| !version.is_opaque())
+                    .cloned()
+                    .collect();
+                // The same version in several roots counts once
+                artifact_versions.dedup();
                 // Don't forget to reverse!
                 // Versions are sorted from lowest to highest in the BTreeMap.
                 // For the version range to work, we need highest to lowest.
                 artifact_versions.reverse();
-                let selection = version_range.select(artifact_versions.as_slice());
-                let mut selection_set = HashSet::new();
-                for version in selection {
-                    selection_set.insert(version);
-                }
+                let selection = version_range.select_versions(artifact_versions.as_slice());
+                let selection_set: HashSet<&Version> = selection.into_iter().collect();
                 // The selection is just a selection based on the version range.
                 // Choose the right method depending on the command, but keep in
                 // mind that the repository should only contain those artifacts
@@ -278,112 +338,346 @@ impl Repository {
                 //
                 // keep: User wants to keep the selected artifacts => remove them from the repo
                 // rm or du: User wants to remove / analyze the artifacts => retain them in the repo
-                match command {
-                    Keep(_, _) => {
-                        for k in selection_set {
-                            artifact.versions.remove(k);
-                        }
+                artifact.versions.retain(|(version, _), _| {
+                    let selected = selection_set.contains(version);
+                    let retain = match command {
+                        Keep(_, _) => !selected,
+                        Remove(_, _) | Du(_) => selected,
+                    };
+                    if !retain {
+                        trace!(
+                            "Skip {}:{}:{}: excluded by the version range",
+                            group.id,
+                            artifact.id,
+                            version
+                        );
                     }
-                    Remove(_, _) | Du(_, _, _) => {
-                        artifact.versions.retain(|k, _| selection_set.contains(k));
+                    retain
+                });
+            }
+        }
+    }
+
+    fn remove_protected(&mut self, protection: &Protection) {
+        let mut protected: usize = 0;
+        for group in self.groups.values_mut() {
+            for artifact in group.artifacts.values_mut() {
+                artifact.versions.retain(|_, version| {
+                    let protect =
+                        protection.protects(group.id.as_str(), artifact.id.as_str(), version);
+                    if protect {
+                        debug!("Skip {}:{}:{}: protected", group.id, artifact.id, version);
+                        protected += 1;
                     }
+                    !protect
+                });
+            }
+            group
+                .artifacts
+                .retain(|_, artifact| !artifact.versions.is_empty());
+        }
+        self.groups.retain(|_, group| !group.artifacts.is_empty());
+        self.protected = protected;
+    }
+
+    // Measures the size of each version and collects its artifact files
+    fn measure(&mut self, disk_usage: &mut DiskUsage) {
+        for group in self.groups.values_mut() {
+            for artifact in group.artifacts.values_mut() {
+                for version in artifact.versions.values_mut() {
+                    self.backend
+                        .measure(version, artifact.id.as_str(), disk_usage);
+                }
+            }
+        }
+    }
+
+    // Reduces the versions to the artifact files selected by the classifier
+    // filter. The size of a version becomes the size of the selected files.
+    // Versions w/o selected files are dropped.
+    fn select_files(&mut self, classifier_filter: &ClassifierFilter, command: &Command) {
+        for group in self.groups.values_mut() {
+            for artifact in group.artifacts.values_mut() {
+                for version in artifact.versions.values_mut() {
+                    // keep: User wants to keep the selected files => retain the others
+                    // rm or du: User wants to remove / analyze the files => retain them
+                    version.files.retain(|file| {
+                        file.artifact
+                            && match command {
+                                Keep(_, _) => !classifier_filter.match_classifier(file),
+                                Remove(_, _) | Du(_) => classifier_filter.match_classifier(file),
+                            }
+                    });
+                    version.bytes = version.files.iter().map(|file| file.bytes).sum();
+                    version.apparent_bytes =
+                        version.files.iter().map(|file| file.apparent_bytes).sum();
                 }
+                artifact
+                    .versions
+                    .retain(|_, version| !version.files.is_empty());
             }
+            group
+                .artifacts
+                .retain(|_, artifact| !artifact.versions.is_empty());
         }
+        self.groups.retain(|_, group| !group.artifacts.is_empty());
     }
 
     fn compute(&mut self) {
         let mut artifacts: usize = 0;
         let mut versions: usize = 0;
+        let mut files: usize = 0;
         let mut repo_bytes: u64 = 0;
+        let mut repo_apparent_bytes: u64 = 0;
 
         for group in self.groups.values_mut() {
             let mut group_bytes: u64 = 0;
+            let mut group_apparent_bytes: u64 = 0;
             for artifact in group.artifacts.values_mut() {
                 let mut artifact_bytes: u64 = 0;
-                for version in artifact.versions.values_mut() {
-                    let mut version_bytes: u64 = 0;
-                    if let Ok(read_dir) = version.path.read_dir() {
-                        for dir_entry in read_dir.flatten() {
-                            if dir_entry.path().is_file() {
-                                if let Ok(meta) = dir_entry.metadata() {
-                                    version_bytes += meta.len()
-                                }
-                            }
-                        }
-                    }
+                let mut artifact_apparent_bytes: u64 = 0;
+                for version in artifact.versions.values() {
                     versions += 1;
-                    version.bytes = version_bytes;
+                    files += version.files.len();
                     artifact_bytes += version.bytes;
+                    artifact_apparent_bytes += version.apparent_bytes;
                 }
                 artifacts += 1;
                 artifact.bytes = artifact_bytes;
+                artifact.apparent_bytes = artifact_apparent_bytes;
                 group_bytes += artifact.bytes;
+                group_apparent_bytes += artifact.apparent_bytes;
             }
             group.bytes = group_bytes;
+            group.apparent_bytes = group_apparent_bytes;
             repo_bytes += group.bytes;
+            repo_apparent_bytes += group.apparent_bytes;
         }
         self.artifacts = artifacts;
         self.versions = versions;
+        self.files = files;
         self.bytes = repo_bytes;
+        self.apparent_bytes = repo_apparent_bytes;
     }
 
-    fn remove_versions(&self, dry_run: bool, list: bool) {
-        let progress_bar = if !dry_run && !list && self.versions > PROGRESS_BAR_THRESHOLD {
-            Some(
-                ProgressBar::new(self.versions as u64)
-                    .with_prefix("Remove artifacts")
-                    .with_style(
-                        ProgressStyle::default_bar()
-                            .progress_chars("#>-")
-                            .template("{prefix} [{wide_bar:.green/yellow}] {pos:>6}/{len:6}")
-                            .unwrap(),
-                    ),
-            )
-        } else {
-            None
-        };
+    // Sums up everything in the scope which is not part of a version: maven
+    // metadata of artifacts and groups, directories which are not a version, leftovers
+    // of failed downloads etc. Files are assigned to the closest group.
+    // Everything else ends up in the bucket of the repository, except the
+    // lock file of mcup.
+    fn compute_unaccounted(&mut self, scopes: &[PathBuf], disk_usage: &mut DiskUsage) {
+        let lock_file = self.path.join(LOCK_FILE);
+        let group_ids: HashMap<PathBuf, String> = self
+            .groups
+            .keys()
+            .flat_map(|group_id| {
+                self.backend
+                    .group_paths(group_id)
+                    .into_iter()
+                    .map(move |group_path| (group_path, group_id.clone()))
+            })
+            .collect();
+
+        for dir_entry in scopes
+            .iter()
+            .flat_map(|scope| {
+                WalkDir::new(scope).into_iter().filter_entry(|dir_entry| {
+                    if dir_entry.path() == lock_file {
+                        false
+                    } else if dir_entry.file_type().is_dir() {
+                        !self.backend.version_dir(dir_entry.path())
+                    } else {
+                        !self.backend.version_file(dir_entry.path())
+                    }
+                })
+            })
+            .filter_map(|e| e.ok())
+        {
+            if dir_entry.file_type().is_symlink() {
+                continue;
+            }
+            if let Ok(meta) = dir_entry.metadata() {
+                let (bytes, apparent_bytes) = disk_usage.file(&meta);
+                let group = dir_entry
+                    .path()
+                    .ancestors()
+                    .find_map(|path| group_ids.get(path))
+                    .and_then(|group_id| self.groups.get_mut(group_id));
+                match group {
+                    Some(group) => {
+                        group.unaccounted_bytes += bytes;
+                        group.unaccounted_apparent_bytes += apparent_bytes;
+                        group.bytes += bytes;
+                        group.apparent_bytes += apparent_bytes;
+                    }
+                    None => {
+                        self.unaccounted_bytes += bytes;
+                        self.unaccounted_apparent_bytes += apparent_bytes;
+                    }
+                }
+                self.bytes += bytes;
+                self.apparent_bytes += apparent_bytes;
+            }
+        }
+    }
 
+    fn remove_versions(&self, dry_run: bool, paths: &mut Vec<PathBuf>) {
+        self.observer.notify(&Event::RemovalStart(self.versions));
         for group in self.groups.values() {
             for artifact in group.artifacts.values() {
                 for version in artifact.versions.values() {
-                    if list {
-                        if let Some(str) = version.path.as_os_str().to_str() {
-                            println!("{str}");
-                        }
-                    } else if let Some(progress_bar) = &progress_bar {
-                        progress_bar.inc(1);
-                    }
+                    let version_paths = self.backend.version_paths(version);
                     if !dry_run {
-                        if let Err(_error) = remove_dir_all(version.path.as_path()) {
-                            if let Some(_path) = version.path.as_path().to_str() {
-                                // TODO error handling / reporting
-                            }
+                        if let Err(error) = self.backend.remove(version) {
+                            self.observer
+                                .notify(&Event::Error(version.path.as_path(), &error));
+                            continue;
                         }
                     }
+                    self.observer.notify(&Event::Remove(&version_paths));
+                    paths.extend(version_paths);
                 }
             }
         }
+        self.observer.notify(&Event::RemovalEnd);
+    }
 
-        if let Some(progress_bar) = &progress_bar {
-            progress_bar.finish_and_clear();
+    fn remove_files(&self, dry_run: bool, paths: &mut Vec<PathBuf>) {
+        self.observer.notify(&Event::RemovalStart(self.files));
+        for group in self.groups.values() {
+            for artifact in group.artifacts.values() {
+                for version in artifact.versions.values() {
+                    for file in &version.files {
+                        if !dry_run {
+                            if let Err(error) = remove_file(file.path.as_path()) {
+                                self.observer
+                                    .notify(&Event::Error(file.path.as_path(), &error.into()));
+                                continue;
+                            }
+                        }
+                        let file_paths = [file.path.clone()];
+                        self.observer.notify(&Event::Remove(&file_paths));
+                        paths.extend(file_paths);
+                    }
+                }
+            }
         }
+        self.observer.notify(&Event::RemovalEnd);
     }
 
     fn prune_empty_directories(&self) {
         for group in self.groups.values() {
             for artifact in group.artifacts.values() {
                 for version in artifact.versions.values() {
-                    let _ = remove_dir(version.path.as_path());
+                    self.backend.prune(version);
                 }
-                // TODO What if only maven metadata is left?
-                let _ = remove_dir(artifact.path.as_path());
             }
-            let _ = remove_dir(group.path.as_path());
         }
     }
 }
 
+// Files in a version directory which don't make a version on their own
+const NO_ARTIFACT_SUFFIXES: [&str; 2] = [".lastUpdated", ".part"];
+
+// Checks whether the directory is a version according to the maven layout
+// 'artifactId/version/artifactId-version[-classifier].ext'. The directory
+// must be named like a valid version and contain at least one artifact file
+// following this naming scheme. Returns whether the version contains a pom.
+pub fn version_dir(path: &Path) -> Option<bool> {
+    let version = Version::from_path(path).ok()?;
+    let version_name = path.file_name()?.to_str()?;
+    let artifact_id = path.parent()?.file_name()?.to_str()?;
+    let mut artifact_files = false;
+    let mut pom = false;
+    for dir_entry in path.read_dir().ok()?.flatten() {
+        if !dir_entry
+            .file_type()
+            .is_ok_and(|file_type| file_type.is_file())
+        {
+            continue;
+        }
+        if let Some(file_name) = dir_entry.file_name().to_str() {
+            if NO_ARTIFACT_SUFFIXES
+                .iter()
+                .any(|suffix| file_name.ends_with(suffix))
+            {
+                continue;
+            }
+            if let Some(suffix) =
+                artifact_file_suffix(artifact_id, version_name, version.snapshot, file_name)
+            {
+                artifact_files = true;
+                pom = pom || suffix == ".pom";
+            }
+        }
+    }
+    if artifact_files {
+        Some(pom)
+    } else {
+        None
+    }
+}
+
+// Explains why a directory which looks like a version is not a version
+// (see version_dir()). Returns None for directories which don't contain files
+// of the artifact, like group and artifact directories.
+pub fn skipped_version_dir(path: &Path) -> Option<String> {
+    let artifact_id = path.parent()?.file_name()?.to_str()?;
+    let artifact_prefix = format!("{artifact_id}-");
+    let artifact_files = path
+        .read_dir()
+        .ok()?
+        .flatten()
+        .filter(|dir_entry| {
+            dir_entry
+                .file_type()
+                .is_ok_and(|file_type| file_type.is_file())
+        })
+        .any(|dir_entry| {
+            dir_entry
+                .file_name()
+                .to_str()
+                .is_some_and(|file_name| file_name.starts_with(&artifact_prefix))
+        });
+    if artifact_files {
+        Some(format!(
+            "No files named '{}-{}[-classifier].ext'",
+            artifact_id,
+            path.file_name()?.to_str()?
+        ))
+    } else {
+        None
+    }
+}
+
+// Returns the part of the file name following 'artifactId-version', i.e.
+// '[-classifier].ext', or None if the file name does not follow the maven
+// layout. Snapshots may use a timestamp instead of 'SNAPSHOT':
+// 'artifactId-1.0-20240101.123456-1.jar' is a file of version '1.0-SNAPSHOT'.
+pub fn artifact_file_suffix<'a>(
+    artifact_id: &str,
+    version: &str,
+    snapshot: bool,
+    file_name: &'a str,
+) -> Option<&'a str> {
+    let rest = file_name.strip_prefix(artifact_id)?.strip_prefix('-')?;
+    let suffix = match rest.strip_prefix(version) {
+        Some(suffix) => suffix,
+        None if snapshot => {
+            let base = version.strip_suffix("-SNAPSHOT")?;
+            let timestamped = rest.strip_prefix(base)?.strip_prefix('-')?;
+            let build = SNAPSHOT_TIMESTAMP.find(timestamped)?;
+            &timestamped[build.end()..]
+        }
+        None => return None,
+    };
+    if suffix.starts_with('.') || suffix.starts_with('-') {
+        Some(suffix)
+    } else {
+        None
+    }
+}
+
 // ------------------------------------------------------ GAV
 
 #[derive(Debug)]
@@ -393,8 +687,93 @@ pub struct Gav {
     pub version: Version,
 }
 
+impl Gav {
+    // Creates the GAV of the version directory 'group/artifactId/version'
+    pub fn new(group_id: &str, version_path: &Path) -> Result<Gav> {
+        let version = Version::from_path(version_path)?;
+
+        let artifact_path = version_path.parent().with_context(|| "No artifact path")?;
+        let artifact_id = artifact_path
+            .file_name()
+            .with_context(|| "No artifact ID")?
+            .to_str()
+            .with_context(|| "No artifact ID")?;
+        let artifact = Artifact::new(artifact_id, artifact_path);
+
+        let group_path = artifact_path.parent().with_context(|| "No group path")?;
+        let group = Group::new(group_id, group_path);
+
+        Ok(Gav {
+            group,
+            artifact,
+            version,
+        })
+    }
+}
+
 impl Display for Gav {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}:{}:{}", self.group.id, self.artifact.id, self.version)
     }
 }
+
+// ------------------------------------------------------ layout tests
+
+#[cfg(test)]
+mod layout_tests {
+    use crate::repo::artifact_file_suffix;
+
+    #[test]
+    fn artifact_files() {
+        assert_eq!(Some(".pom"), suffix("foo", "1.0", "foo-1.0.pom"));
+        assert_eq!(Some(".jar.sha1"), suffix("foo", "1.0", "foo-1.0.jar.sha1"));
+        assert_eq!(
+            Some("-sources.jar"),
+            suffix("foo", "1.0", "foo-1.0-sources.jar")
+        );
+        assert_eq!(
+            Some("-linux-x86_64.so"),
+            suffix("foo-bar", "1.0", "foo-bar-1.0-linux-x86_64.so")
+        );
+    }
+
+    #[test]
+    fn snapshot_files() {
+        assert_eq!(
+            Some(".jar"),
+            suffix("foo", "1.0-SNAPSHOT", "foo-1.0-SNAPSHOT.jar")
+        );
+        assert_eq!(
+            Some(".jar"),
+            suffix("foo", "1.0-SNAPSHOT", "foo-1.0-20240101.123456-1.jar")
+        );
+        assert_eq!(
+            Some("-tests.jar"),
+            suffix(
+                "foo",
+                "1.0-SNAPSHOT",
+                "foo-1.0-20240101.123456-12-tests.jar"
+            )
+        );
+        assert_eq!(None, suffix("foo", "1.0-SNAPSHOT", "foo-1.0-2024.jar"));
+    }
+
+    #[test]
+    fn no_artifact_files() {
+        assert_eq!(None, suffix("foo", "1.0", "_remote.repositories"));
+        assert_eq!(None, suffix("foo", "1.0", "maven-metadata-local.xml"));
+        assert_eq!(None, suffix("foo", "1.0", "bar-1.0.jar"));
+        assert_eq!(None, suffix("foo", "1.0", "foo-1.01.jar"));
+        assert_eq!(None, suffix("foo", "1.0", "foo-2.0.jar"));
+        assert_eq!(None, suffix("foo", "1.0-SNAPSHOT", "foo-1.0-latest.jar"));
+    }
+
+    fn suffix<'a>(artifact_id: &str, version: &str, file_name: &'a str) -> Option<&'a str> {
+        artifact_file_suffix(
+            artifact_id,
+            version,
+            version.ends_with("-SNAPSHOT"),
+            file_name,
+        )
+    }
+}
@@ -0,0 +1,555 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs::{canonicalize, read_to_string};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use directories::BaseDirs;
+use log::warn;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+// system property to override the local repository
+const MAVEN_REPO_LOCAL: &str = "maven.repo.local";
+// project configuration relative to the project root
+const MVN_DIR: &str = ".mvn";
+const MAVEN_CONFIG: &str = ".mvn/maven.config";
+const JVM_CONFIG: &str = ".mvn/jvm.config";
+
+// ------------------------------------------------------ local repository
+
+// The options which override the configuration of maven. Relative paths
+// are resolved against the current directory.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct MavenLocation {
+    pub local_repository: Option<PathBuf>,
+    pub project: Option<PathBuf>,
+    pub settings: Option<PathBuf>,
+    pub global_settings: Option<PathBuf>,
+}
+
+// Locates the local repository like maven does:
+//   1. The value of `local_repository` (`--local-repository`)
+//   2. The system property `maven.repo.local` in `MAVEN_ARGS` or `MAVEN_OPTS`
+//   3. The system property `maven.repo.local` in `.mvn/maven.config` or
+//      `.mvn/jvm.config` of the project (`project` or the current directory)
+//   4. The value of `<localRepository/>` in the user settings
+//      (`settings`, `-s` in `MAVEN_ARGS` or `~/.m2/settings.xml`)
+//   5. The value of `<localRepository/>` in the global settings
+//      (`global_settings`, `-gs` in `MAVEN_ARGS` or `$MAVEN_HOME/conf/settings.xml`)
+//   6. Fall back to `~/.m2/repository/`
+// Settings which can't be parsed are skipped with a warning.
+// Values are interpolated using environment variables (`${env.NAME}`), system and
+// user properties and the properties of the active profiles in the settings.
+// Relative paths are resolved against the project root (if any).
+// Returns the path and the properties used to detect the layout of the repository.
+pub fn local_repository(location: &MavenLocation) -> Result<(PathBuf, HashMap<String, String>)> {
+    if let Some(path) = &location.local_repository {
+        let mut options = MavenOptions::default();
+        options.read_env();
+        return Ok((path.clone(), options.properties));
+    }
+
+    let base_dirs = BaseDirs::new().with_context(|| "No home directory")?;
+    let home = base_dirs.home_dir();
+    let project = match &location.project {
+        Some(dir) => Some(project_root(dir)?),
+        None => env::current_dir()
+            .ok()
+            .and_then(|dir| find_project_root(&dir)),
+    };
+    let mut options = MavenOptions::default();
+    if let Some(project) = &project {
+        options.read_project(project)?;
+    }
+    options.read_env();
+    let maven_home = maven_home();
+
+    let user_settings = match &location.settings {
+        Some(path) => Some(Settings::read(path)?),
+        None => match &options.settings {
+            Some(path) => Some(Settings::read(path)?),
+            None => Settings::read_optional(&home.join(".m2/settings.xml"))?,
+        },
+    };
+    let global_settings = match &location.global_settings {
+        Some(path) => Some(Settings::read(path)?),
+        None => match (&options.global_settings, &maven_home) {
+            (Some(path), _) => Some(Settings::read(path)?),
+            (None, Some(maven_home)) => {
+                Settings::read_optional(&maven_home.join("conf/settings.xml"))?
+            }
+            (None, None) => None,
+        },
+    };
+
+    // properties in order of precedence
+    let mut properties: HashMap<String, String> = HashMap::new();
+    for settings in [&global_settings, &user_settings].into_iter().flatten() {
+        properties.extend(settings.properties.clone());
+    }
+    properties.insert("user.home".to_string(), home.display().to_string());
+    if let Ok(current_dir) = env::current_dir() {
+        properties.insert("user.dir".to_string(), current_dir.display().to_string());
+    }
+    if let Some(maven_home) = &maven_home {
+        properties.insert("maven.home".to_string(), maven_home.display().to_string());
+    }
+    if let Some(project) = &project {
+        properties.insert(
+            "maven.multiModuleProjectDirectory".to_string(),
+            project.display().to_string(),
+        );
+    }
+    properties.extend(options.properties.clone());
+    let lookup = |name: &str| match name.strip_prefix("env.") {
+        Some(variable) => env::var(variable).ok(),
+        None => properties.get(name).cloned(),
+    };
+
+    let local_repository = options
+        .properties
+        .get(MAVEN_REPO_LOCAL)
+        .or_else(|| {
+            user_settings
+                .as_ref()
+                .and_then(|s| s.local_repository.as_ref())
+        })
+        .or_else(|| {
+            global_settings
+                .as_ref()
+                .and_then(|s| s.local_repository.as_ref())
+        });
+    let path = match local_repository {
+        Some(local_repository) => {
+            let path = PathBuf::from(interpolate(local_repository, lookup));
+            match &project {
+                Some(project) if path.is_relative() => project.join(path),
+                _ => path,
+            }
+        }
+        None => home.join(".m2/repository"),
+    };
+    Ok((path, properties))
+}
+
+// Returns the first directory containing '.mvn/' walking up from 'dir'
+fn project_root(dir: &Path) -> Result<PathBuf> {
+    let dir = canonicalize(dir).with_context(|| format!("Unable to find '{}'", dir.display()))?;
+    match find_project_root(&dir) {
+        Some(root) => Ok(root),
+        None => bail!(
+            "No maven project with '.mvn/' found in or above '{}'",
+            dir.display()
+        ),
+    }
+}
+
+// Like project_root(), but returns None if there's no project
+fn find_project_root(dir: &Path) -> Option<PathBuf> {
+    let dir = canonicalize(dir).ok()?;
+    dir.ancestors()
+        .find(|path| path.join(MVN_DIR).is_dir())
+        .map(Path::to_path_buf)
+}
+
+// Returns the maven installation from MAVEN_HOME, M2_HOME or the location of 'mvn' in the PATH
+fn maven_home() -> Option<PathBuf> {
+    env::var_os("MAVEN_HOME")
+        .or_else(|| env::var_os("M2_HOME"))
+        .map(PathBuf::from)
+        .or_else(|| {
+            env::split_paths(&env::var_os("PATH")?)
+                .map(|path| path.join("mvn"))
+                .find(|mvn| mvn.is_file())
+                .and_then(|mvn| canonicalize(mvn).ok())
+                .and_then(|mvn| Some(mvn.parent()?.parent()?.to_path_buf()))
+        })
+}
+
+// Replaces expressions like '${user.home}' or '${env.M2_REPO}'. Unknown
+// expressions are left as they are.
+fn interpolate<F>(value: &str, lookup: F) -> String
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut result = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        match rest[start..].find('}') {
+            Some(end) => {
+                let expression = &rest[start..start + end + 1];
+                match lookup(&expression[2..expression.len() - 1]) {
+                    Some(replacement) => result.push_str(replacement.as_str()),
+                    None => result.push_str(expression),
+                }
+                rest = &rest[start + end + 1..];
+            }
+            None => {
+                result.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+// ------------------------------------------------------ maven options
+
+// Options passed to maven using the environment variables MAVEN_ARGS
+// (command line arguments) and MAVEN_OPTS (JVM options) or the project
+// configuration in '.mvn/maven.config' and '.mvn/jvm.config'. Options read
+// later take precedence.
+#[derive(Debug, Default)]
+struct MavenOptions {
+    properties: HashMap<String, String>,
+    settings: Option<PathBuf>,
+    global_settings: Option<PathBuf>,
+}
+
+impl MavenOptions {
+    fn read_env(&mut self) {
+        for variable in ["MAVEN_OPTS", "MAVEN_ARGS"] {
+            if let Ok(value) = env::var(variable) {
+                self.parse(value.as_str());
+            }
+        }
+    }
+
+    // Paths to settings files are resolved against the project root.
+    fn read_project(&mut self, project: &Path) -> Result<()> {
+        for config in [JVM_CONFIG, MAVEN_CONFIG] {
+            let path = project.join(config);
+            if path.exists() {
+                let content = read_to_string(&path)
+                    .with_context(|| format!("Unable to read '{}'", path.display()))?;
+                let content: Vec<&str> = content
+                    .lines()
+                    .filter(|line| !line.trim_start().starts_with('#'))
+                    .collect();
+                self.parse(content.join("\n").as_str());
+            }
+        }
+        self.settings = self.settings.take().map(|path| project.join(path));
+        self.global_settings = self.global_settings.take().map(|path| project.join(path));
+        Ok(())
+    }
+
+    fn parse(&mut self, value: &str) {
+        let mut args = split(value).into_iter();
+        while let Some(arg) = args.next() {
+            if let Some(property) = arg.strip_prefix("-D") {
+                let property = if property.is_empty() {
+                    args.next().unwrap_or_default()
+                } else {
+                    property.to_string()
+                };
+                match property.split_once('=') {
+                    Some((name, value)) => self.properties.insert(name.into(), value.into()),
+                    None => self.properties.insert(property, "true".into()),
+                };
+            } else if arg == "-s" || arg == "--settings" {
+                self.settings = args.next().map(PathBuf::from);
+            } else if let Some(path) = arg.strip_prefix("--settings=") {
+                self.settings = Some(PathBuf::from(path));
+            } else if arg == "-gs" || arg == "--global-settings" {
+                self.global_settings = args.next().map(PathBuf::from);
+            } else if let Some(path) = arg.strip_prefix("--global-settings=") {
+                self.global_settings = Some(PathBuf::from(path));
+            }
+        }
+    }
+}
+
+// Splits a value at whitespaces, but keeps single and double quoted parts together
+fn split(value: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut arg = String::new();
+    let mut quote: Option<char> = None;
+    let mut pending = false;
+    for c in value.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => arg.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                pending = true;
+            }
+            None if c.is_whitespace() => {
+                if pending || !arg.is_empty() {
+                    args.push(std::mem::take(&mut arg));
+                    pending = false;
+                }
+            }
+            None => arg.push(c),
+        }
+    }
+    if pending || !arg.is_empty() {
+        args.push(arg);
+    }
+    args
+}
+
+// ------------------------------------------------------ settings
+
+#[derive(Debug, Default)]
+struct Settings {
+    local_repository: Option<String>,
+    // properties of the active profiles
+    properties: HashMap<String, String>,
+}
+
+#[derive(Default)]
+struct Profile {
+    id: String,
+    active_by_default: bool,
+    properties: HashMap<String, String>,
+}
+
+impl Settings {
+    // Returns None if the settings can't be parsed, so that
+    // the next source in the resolution order is used.
+    fn read(path: &Path) -> Result<Settings> {
+        let content =
+            read_to_string(path).with_context(|| format!("Unable to read '{}'", path.display()))?;
+        Settings::parse(content.as_str())
+            .with_context(|| format!("Unable to parse '{}'", path.display()))
+    }
+
+    // Reads the settings at a default location. Other than the settings given
+    // explicitly, these are skipped with a warning if they can't be parsed.
+    fn read_optional(path: &Path) -> Result<Option<Settings>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content =
+            read_to_string(path).with_context(|| format!("Unable to read '{}'", path.display()))?;
+        match Settings::parse(content.as_str()) {
+            Ok(settings) => Ok(Some(settings)),
+            Err(e) => {
+                warn!("Ignore '{}': Unable to parse settings: {e}", path.display());
+                Ok(None)
+            }
+        }
+    }
+
+    fn parse(content: &str) -> Result<Settings> {
+        let mut reader = Reader::from_str(content);
+        reader.config_mut().trim_text(true);
+
+        let mut local_repository = None;
+        let mut profiles: Vec<Profile> = Vec::new();
+        let mut active_profiles: Vec<String> = Vec::new();
+        let mut elements: Vec<String> = Vec::new();
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) => {
+                    let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+                    if elements == ["settings", "profiles"] && name == "profile" {
+                        profiles.push(Profile::default());
+                    }
+                    elements.push(name);
+                }
+                Ok(Event::End(_)) => {
+                    elements.pop();
+                }
+                Ok(Event::Text(e)) => {
+                    let text = e.decode()?.into_owned();
+                    let path: Vec<&str> = elements.iter().map(String::as_str).collect();
+                    match path.as_slice() {
+                        ["settings", "localRepository"] => local_repository = Some(text),
+                        ["settings", "activeProfiles", "activeProfile"] => {
+                            active_profiles.push(text)
+                        }
+                        ["settings", "profiles", "profile", rest @ ..] => {
+                            if let Some(profile) = profiles.last_mut() {
+                                match rest {
+                                    ["id"] => profile.id = text,
+                                    ["activation", "activeByDefault"] => {
+                                        profile.active_by_default = text == "true"
+                                    }
+                                    ["properties", property] => {
+                                        profile.properties.insert(property.to_string(), text);
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => bail!("{} at position {}", e, reader.error_position()),
+                _ => {}
+            }
+        }
+
+        // profiles listed in <activeProfiles/> deactivate the profiles active by default
+        let explicit = profiles.iter().any(|p| active_profiles.contains(&p.id));
+        let mut properties = HashMap::new();
+        for profile in profiles {
+            let active = if explicit {
+                active_profiles.contains(&profile.id)
+            } else {
+                profile.active_by_default
+            };
+            if active {
+                properties.extend(profile.properties);
+            }
+        }
+        Ok(Settings {
+            local_repository,
+            properties,
+        })
+    }
+}
+
+// ------------------------------------------------------ settings tests
+
+#[cfg(test)]
+mod settings_tests {
+    use std::collections::HashMap;
+
+    use crate::settings::{
+        find_project_root, interpolate, project_root, split, MavenOptions, Settings,
+    };
+    use crate::temp::TempDir;
+
+    #[test]
+    fn interpolation() {
+        let properties = HashMap::from([
+            ("user.home".to_string(), "/home/foo".to_string()),
+            ("env.M2_REPO".to_string(), "/opt/m2".to_string()),
+        ]);
+        let lookup = |name: &str| properties.get(name).cloned();
+        assert_eq!("/home/foo/repo", interpolate("${user.home}/repo", lookup));
+        assert_eq!("/opt/m2", interpolate("${env.M2_REPO}", lookup));
+        assert_eq!(
+            "/opt/m2/home/foo",
+            interpolate("${env.M2_REPO}${user.home}", lookup)
+        );
+        assert_eq!("${unknown}/repo", interpolate("${unknown}/repo", lookup));
+        assert_eq!(
+            "/repo/${user.home",
+            interpolate("/repo/${user.home", lookup)
+        );
+        assert_eq!("/repo", interpolate("/repo", lookup));
+    }
+
+    #[test]
+    fn options() {
+        let mut options = MavenOptions::default();
+        options
+            .parse(r#"-Xmx1g -Dmaven.repo.local="/tmp/my repo" -D skipTests -s /etc/settings.xml"#);
+        options.parse("--global-settings=/etc/global.xml");
+        assert_eq!(
+            Some(&"/tmp/my repo".to_string()),
+            options.properties.get("maven.repo.local")
+        );
+        assert_eq!(
+            Some(&"true".to_string()),
+            options.properties.get("skipTests")
+        );
+        assert_eq!(Some("/etc/settings.xml".into()), options.settings);
+        assert_eq!(Some("/etc/global.xml".into()), options.global_settings);
+    }
+
+    #[test]
+    fn project() {
+        let project = TempDir::new("project");
+        let module = project.dir("module/src");
+        project.file(
+            ".mvn/maven.config",
+            "# project repository\n-Dmaven.repo.local=.m2/repository\n--settings .mvn/settings.xml\n",
+        );
+        project.file(".mvn/jvm.config", "-Xmx2g -Dmaven.repo.local=/jvm\n");
+
+        let root = project_root(&module).unwrap();
+        assert_eq!(Some(root.clone()), find_project_root(&module));
+        assert_eq!(None, find_project_root(&TempDir::new("no-project").path));
+        let mut options = MavenOptions::default();
+        options.read_project(&root).unwrap();
+        assert_eq!(
+            Some(&".m2/repository".to_string()),
+            options.properties.get("maven.repo.local")
+        );
+        assert_eq!(Some(root.join(".mvn/settings.xml")), options.settings);
+    }
+
+    #[test]
+    fn quotes() {
+        assert_eq!(vec!["a", "b c", "", "d"], split(r#" a "b c" '' d "#));
+    }
+
+    #[test]
+    fn settings() {
+        let settings = Settings::parse(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<settings>
+    <localRepository>${repo.base}/repository</localRepository>
+    <profiles>
+        <profile>
+            <id>default</id>
+            <activation><activeByDefault>true</activeByDefault></activation>
+            <properties><repo.base>/default</repo.base></properties>
+        </profile>
+        <profile>
+            <id>work</id>
+            <properties><repo.base>/work</repo.base></properties>
+        </profile>
+    </profiles>
+    <activeProfiles>
+        <activeProfile>work</activeProfile>
+    </activeProfiles>
+</settings>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            Some("${repo.base}/repository".to_string()),
+            settings.local_repository
+        );
+        assert_eq!(
+            Some(&"/work".to_string()),
+            settings.properties.get("repo.base")
+        );
+    }
+
+    #[test]
+    fn defaults() {
+        let settings = Settings::parse(
+            r#"<settings>
+    <profiles>
+        <profile>
+            <id>default</id>
+            <activation><activeByDefault>true</activeByDefault></activation>
+            <properties><repo.base>/default</repo.base></properties>
+        </profile>
+    </profiles>
+</settings>"#,
+        )
+        .unwrap();
+        assert_eq!(None, settings.local_repository);
+        assert_eq!(
+            Some(&"/default".to_string()),
+            settings.properties.get("repo.base")
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        let temp = TempDir::new("malformed-settings");
+        let path = temp.file(
+            "settings.xml",
+            "<settings><localRepository>/repo</settings>",
+        );
+        assert!(Settings::parse("<settings><localRepository>/repo</settings>").is_err());
+        assert!(Settings::read(&path).is_err());
+        assert!(Settings::read_optional(&path).unwrap().is_none());
+        assert!(Settings::read(&temp.join("missing.xml")).is_err());
+        assert!(Settings::read_optional(&temp.join("missing.xml"))
+            .unwrap()
+            .is_none());
+    }
+}
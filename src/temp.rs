@@ -0,0 +1,47 @@
+use std::env;
+use std::fs::{create_dir_all, remove_dir_all, write};
+use std::path::{Path, PathBuf};
+
+// ------------------------------------------------------ temp dir
+
+// A directory in the temp directory of the system used by the unit tests. The
+// directory is removed when dropped, so failing tests don't leave it behind.
+pub struct TempDir {
+    pub path: PathBuf,
+}
+
+impl TempDir {
+    pub fn new(name: &str) -> TempDir {
+        let path = env::temp_dir().join(format!("mcup-{}-{}", name, std::process::id()));
+        if path.exists() {
+            remove_dir_all(&path).unwrap();
+        }
+        create_dir_all(&path).unwrap();
+        TempDir { path }
+    }
+
+    pub fn join(&self, path: impl AsRef<Path>) -> PathBuf {
+        self.path.join(path)
+    }
+
+    // A directory relative to the temp dir including its parents
+    pub fn dir(&self, path: impl AsRef<Path>) -> PathBuf {
+        let path = self.path.join(path);
+        create_dir_all(&path).unwrap();
+        path
+    }
+
+    // A file relative to the temp dir including its parent directories
+    pub fn file(&self, path: impl AsRef<Path>, content: impl AsRef<[u8]>) -> PathBuf {
+        let path = self.path.join(path);
+        create_dir_all(path.parent().unwrap()).unwrap();
+        write(&path, content).unwrap();
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = remove_dir_all(&self.path);
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::HashSet;
+use std::fs::Metadata;
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
+
+// ------------------------------------------------------ disk usage
+
+// Sums up file sizes the same way as 'du' does: Files and directories are
+// measured by the blocks allocated on disk and by their apparent length.
+// Files with more than one hard link are counted only once.
+#[derive(Debug, Default)]
+pub struct DiskUsage {
+    inodes: HashSet<(u64, u64)>,
+}
+
+impl DiskUsage {
+    pub fn new() -> DiskUsage {
+        DiskUsage {
+            inodes: HashSet::new(),
+        }
+    }
+
+    // Returns (bytes, apparent bytes) of the file,
+    // or (0, 0) if the file has already been counted.
+    #[cfg(unix)]
+    pub fn file(&mut self, meta: &Metadata) -> (u64, u64) {
+        if meta.nlink() > 1 && !self.inodes.insert((meta.dev(), meta.ino())) {
+            (0, 0)
+        } else {
+            // st_blocks is always in units of 512 bytes
+            (meta.blocks() * 512, meta.len())
+        }
+    }
+
+    // There's no portable way to get the allocated size or
+    // the inode outside unix, so use the file length only.
+    #[cfg(not(unix))]
+    pub fn file(&mut self, meta: &Metadata) -> (u64, u64) {
+        (meta.len(), meta.len())
+    }
+}
+
+// ------------------------------------------------------ disk usage tests
+
+#[cfg(all(test, unix))]
+mod disk_usage_tests {
+    use std::fs::{hard_link, File};
+
+    use crate::temp::TempDir;
+    use crate::usage::DiskUsage;
+
+    #[test]
+    fn blocks() {
+        let temp = TempDir::new("usage-blocks");
+        // rounded up to whole blocks
+        let file = temp.file("file", vec![b'x'; 5000]);
+        let (bytes, apparent_bytes) = DiskUsage::new().file(&file.metadata().unwrap());
+        assert_eq!(5000, apparent_bytes);
+        assert!(bytes > apparent_bytes);
+        assert_eq!(0, bytes % 512);
+
+        // a sparse file has no blocks allocated for its holes
+        let sparse = temp.join("sparse");
+        File::create(&sparse)
+            .unwrap()
+            .set_len(10 * 1024 * 1024)
+            .unwrap();
+        let (bytes, apparent_bytes) = DiskUsage::new().file(&sparse.metadata().unwrap());
+        assert_eq!(10 * 1024 * 1024, apparent_bytes);
+        assert!(bytes < apparent_bytes);
+    }
+
+    #[test]
+    fn hard_links() {
+        let temp = TempDir::new("usage-hard-links");
+        let file = temp.file("file", vec![b'x'; 5000]);
+        let link = temp.join("link");
+        hard_link(&file, &link).unwrap();
+
+        let mut disk_usage = DiskUsage::new();
+        let (bytes, apparent_bytes) = disk_usage.file(&file.metadata().unwrap());
+        assert!(bytes >= 5000);
+        assert_eq!(5000, apparent_bytes);
+        assert_eq!((0, 0), disk_usage.file(&link.metadata().unwrap()));
+        assert_eq!((0, 0), disk_usage.file(&file.metadata().unwrap()));
+
+        // another measurement counts the file again
+        assert_eq!(
+            (bytes, apparent_bytes),
+            DiskUsage::new().file(&link.metadata().unwrap())
+        );
+    }
+}
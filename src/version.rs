@@ -1,44 +1,120 @@
 use fmt::Display;
-use std::cmp::min;
+use std::cmp::{min, Ordering};
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Formatter;
+use std::fs::{metadata, read_to_string};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::{bail, Context, Result};
-use clap::ArgMatches;
+use regex::Regex;
 
-use crate::version::ReleaseType::{Releases, Snapshots};
-use crate::version::VersionRange::{Exact, Latest, Oldest};
+use crate::file::ArtifactFile;
+use crate::layout::Prefix;
+use crate::origin::Origin;
+use crate::version::VersionLine::{Major, Minor};
+use crate::version::VersionRange::{Exact, Latest, LatestPer, Oldest, Since, Until};
 use std::hash::{Hash, Hasher};
 
+lazy_static! {
+    // <lastUpdated>yyyyMMddHHmmss</lastUpdated> of the snapshot metadata
+    static ref LAST_UPDATED: Regex =
+        Regex::new(r"<lastUpdated>(\d{4})(\d{2})(\d{2})(\d{2})(\d{2})(\d{2})</lastUpdated>").unwrap();
+    static ref DATE: Regex = Regex::new(r"^(\d{4})-(\d{2})-(\d{2})$").unwrap();
+}
+
 // ------------------------------------------------------ version
 
 // Maven version number (unfortunately, we cannot use SemVer here)
-#[derive(Debug, Clone, Ord, PartialOrd)]
+#[derive(Debug, Clone)]
 pub struct Version {
     pub major: Option<u32>,
     pub minor: Option<u32>,
     pub patch: Option<u32>,
     pub qualifier: Option<String>,
     pub snapshot: bool,
+    pub pom: bool,
+    pub origin: Origin,
+    // installed or cached in a split repository
+    pub prefix: Option<Prefix>,
+    // the name of the version directory or the ivy revision like '1.0.0-Beta'
+    pub name: String,
     pub path: PathBuf,
+    // descriptors stored outside the version directory (gradle)
+    pub descriptors: Vec<PathBuf>,
     pub bytes: u64,
+    pub apparent_bytes: u64,
+    pub files: Vec<ArtifactFile>,
 }
 
 impl Version {
+    // Directories which are not named like a valid version become opaque versions
     pub fn from_path(path: &Path) -> Result<Version> {
         let version = path
             .file_name()
             .with_context(|| "No version")?
             .to_str()
             .with_context(|| "No version")?;
-        let mut version = Version::from_str(version)?;
+        let mut version = Version::parse(version).unwrap_or_else(|_| Version::opaque(version));
         version.path = path.to_path_buf();
         Ok(version)
     }
 
+    // A version which doesn't follow the maven version scheme like 'latest',
+    // '${revision}', 'master-SNAPSHOT' or a commit hash. Opaque versions have
+    // no major version, so they're ordered below all other versions and by
+    // name among themselves.
+    pub fn opaque(version: &str) -> Version {
+        let (name, snapshot) = match version.strip_suffix("-SNAPSHOT") {
+            Some(name) if !name.is_empty() => (name, true),
+            _ => (version, false),
+        };
+        Version {
+            major: None,
+            minor: None,
+            patch: None,
+            qualifier: Some(String::from(name)),
+            snapshot,
+            pom: true,
+            origin: Origin::default(),
+            prefix: None,
+            name: String::from(version),
+            path: PathBuf::new(),
+            descriptors: Vec::new(),
+            bytes: 0,
+            apparent_bytes: 0,
+            files: Vec::new(),
+        }
+    }
+
+    pub fn is_opaque(&self) -> bool {
+        self.major.is_none()
+    }
+
+    // When the version has been installed or downloaded: the latest
+    // '<lastUpdated>' of the snapshot metadata or the modification time of
+    // the version directory.
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.path
+            .read_dir()
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|dir_entry| {
+                dir_entry.file_name().to_str().is_some_and(|file_name| {
+                    file_name.starts_with("maven-metadata") && file_name.ends_with(".xml")
+                })
+            })
+            .filter_map(|dir_entry| read_to_string(dir_entry.path()).ok())
+            .filter_map(|xml| last_updated(xml.as_str()))
+            .max()
+            .or_else(|| metadata(&self.path).and_then(|meta| meta.modified()).ok())
+    }
+
     //noinspection DuplicatedCode
-    pub fn from_str(version: &str) -> Result<Version> {
+    pub fn parse(version: &str) -> Result<Version> {
         let input = version;
         let (version, snapshot) = match version.strip_suffix("-SNAPSHOT") {
             Some(ver) => (ver, true),
@@ -133,8 +209,15 @@ impl Version {
                 patch: mmp[2],
                 qualifier: qual,
                 snapshot,
+                pom: true,
+                origin: Origin::default(),
+                prefix: None,
+                name: String::from(input),
                 path: PathBuf::new(),
+                descriptors: Vec::new(),
                 bytes: 0,
+                apparent_bytes: 0,
+                files: Vec::new(),
             }),
             None => bail!("Invalid version: '{}'", input),
         }
@@ -153,6 +236,40 @@ impl PartialEq for Version {
 
 impl Eq for Version {}
 
+impl FromStr for Version {
+    type Err = anyhow::Error;
+
+    fn from_str(version: &str) -> Result<Version> {
+        Version::parse(version)
+    }
+}
+
+// Versions are ordered by the same components as they're compared by PartialEq
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (
+            self.major,
+            self.minor,
+            self.patch,
+            &self.qualifier,
+            self.snapshot,
+        )
+            .cmp(&(
+                other.major,
+                other.minor,
+                other.patch,
+                &other.qualifier,
+                other.snapshot,
+            ))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Hash for Version {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.major.hash(state);
@@ -175,7 +292,11 @@ impl Display for Version {
             write!(f, ".{i}")?;
         }
         if let Some(q) = &self.qualifier {
-            write!(f, ".{q}")?;
+            if self.is_opaque() {
+                write!(f, "{q}")?;
+            } else {
+                write!(f, ".{q}")?;
+            }
         }
         write!(f, "{}", if self.snapshot { "-SNAPSHOT" } else { "" })
     }
@@ -188,20 +309,48 @@ pub enum VersionRange {
     Latest(usize),
     Oldest(usize),
     Exact(Version),
+    // versions modified at or after the given time ('since:<date>', 'age<<n>')
+    Since(SystemTime),
+    // versions modified before the given time ('until:<date>', 'age><n>')
+    Until(SystemTime),
+    // the n most recent versions of each major or minor line ('latest-per:minor=<n>')
+    LatestPer(VersionLine, usize),
 }
 
 impl VersionRange {
-    pub fn from(args: &ArgMatches) -> Option<VersionRange> {
-        args.get_one::<VersionRange>("versions").cloned()
-    }
-
     pub fn parse(version: &str) -> Result<VersionRange> {
-        if let Some(count) = version.strip_suffix("..") {
+        let date = |date: &str| {
+            parse_date(date).with_context(|| {
+                format!("Illegal version range: {version}. Use an existing date <yyyy>-<mm>-<dd>.")
+            })
+        };
+        if let Some(since) = version.strip_prefix("since:") {
+            Ok(Since(date(since)?))
+        } else if let Some(until) = version.strip_prefix("until:") {
+            Ok(Until(date(until)?))
+        } else if let Some(age) = version.strip_prefix("age<") {
+            Ok(Since(parse_age(age)?))
+        } else if let Some(age) = version.strip_prefix("age>") {
+            Ok(Until(parse_age(age)?))
+        } else if let Some(retention) = version.strip_prefix("latest-per:") {
+            match retention.split_once('=') {
+                Some(("major", count)) => {
+                    VersionRange::extract_versions(version, count, |n| LatestPer(Major, n))
+                }
+                Some(("minor", count)) => {
+                    VersionRange::extract_versions(version, count, |n| LatestPer(Minor, n))
+                }
+                _ => bail!(
+                    "Illegal version range: {}. Use 'latest-per:major=<n>' or 'latest-per:minor=<n>'.",
+                    version
+                ),
+            }
+        } else if let Some(count) = version.strip_suffix("..") {
             VersionRange::extract_versions(version, count, Latest)
         } else if let Some(count) = version.strip_prefix("..") {
             VersionRange::extract_versions(version, count, Oldest)
         } else {
-            match Version::from_str(version) {
+            match Version::parse(version) {
                 Ok(v) => Ok(Exact(v)),
                 Err(e) => bail!("{}", e),
             }
@@ -211,7 +360,7 @@ impl VersionRange {
     fn extract_versions(
         version: &str,
         count: &str,
-        range_fn: fn(usize) -> VersionRange,
+        range_fn: impl Fn(usize) -> VersionRange,
     ) -> Result<VersionRange> {
         match count.parse::<usize>() {
             Ok(n) => {
@@ -244,6 +393,144 @@ impl VersionRange {
             _ => slice,
         }
     }
+
+    // Select versions according to the version range. The versions must be
+    // sorted from highest to lowest.
+    pub fn select_versions<'a>(&self, versions: &'a [Version]) -> Vec<&'a Version> {
+        match self {
+            LatestPer(line, n) => {
+                let mut counts = HashMap::new();
+                versions
+                    .iter()
+                    .filter(|version| {
+                        let count = counts.entry(line.of(version)).or_insert(0);
+                        *count += 1;
+                        *count <= *n
+                    })
+                    .collect()
+            }
+            _ => self.select(versions).iter().collect(),
+        }
+    }
+
+    // Whether an exact or time based range contains the version. Count based
+    // ranges depend on the other versions, see select().
+    pub fn contains(&self, version: &Version) -> bool {
+        match self {
+            Latest(_) | Oldest(_) | LatestPer(_, _) => true,
+            Exact(exact) => exact == version,
+            Since(time) => version.modified().is_some_and(|modified| modified >= *time),
+            Until(time) => version.modified().is_some_and(|modified| modified < *time),
+        }
+    }
+
+    // Whether an exact or time based range excludes the version. Unlike
+    // !contains(), a version of unknown age is neither contained nor excluded.
+    pub fn excludes(&self, version: &Version) -> bool {
+        match self {
+            Latest(_) | Oldest(_) | LatestPer(_, _) => false,
+            Exact(exact) => exact != version,
+            Since(time) => version.modified().is_some_and(|modified| modified < *time),
+            Until(time) => version.modified().is_some_and(|modified| modified >= *time),
+        }
+    }
+}
+
+// Parses dates like '2024-01-01' (UTC)
+fn parse_date(date: &str) -> Option<SystemTime> {
+    let captures = DATE.captures(date)?;
+    let number = |index: usize| captures[index].parse::<u64>().ok();
+    utc(number(1)?, number(2)?, number(3)?, 0, 0, 0)
+}
+
+// Parses ages like '90d', '2w', '6m' or '1y' relative to now
+fn parse_age(age: &str) -> Result<SystemTime> {
+    let (value, unit) = age.split_at(age.find(|c: char| !c.is_ascii_digit()).unwrap_or(age.len()));
+    let days = match unit {
+        "d" => 1,
+        "w" => 7,
+        "m" => 30,
+        "y" => 365,
+        _ => bail!("Illegal age: {}. Use <n>[d|w|m|y].", age),
+    };
+    match value.parse::<u64>() {
+        Ok(n) => n
+            .checked_mul(days * 24 * 60 * 60)
+            .and_then(|seconds| SystemTime::now().checked_sub(Duration::from_secs(seconds)))
+            .with_context(|| format!("Illegal age: {age}")),
+        Err(_) => bail!("Illegal age: {}. Use <n>[d|w|m|y].", age),
+    }
+}
+
+// Parses the '<lastUpdated>' of maven metadata (UTC)
+fn last_updated(xml: &str) -> Option<SystemTime> {
+    let captures = LAST_UPDATED.captures(xml)?;
+    let number = |index: usize| captures[index].parse::<u64>().ok();
+    utc(
+        number(1)?,
+        number(2)?,
+        number(3)?,
+        number(4)?,
+        number(5)?,
+        number(6)?,
+    )
+}
+
+// Converts a UTC date and time to the system time using the days since the
+// epoch of the proleptic gregorian calendar (see 'days_from_civil' by Howard Hinnant)
+fn utc(year: u64, month: u64, day: u64, hour: u64, minute: u64, second: u64) -> Option<SystemTime> {
+    if !(1970..=9999).contains(&year)
+        || !(1..=12).contains(&month)
+        || !(1..=days_of_month(year, month)).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return None;
+    }
+    let (year, month) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146097 + day_of_era - 719468;
+    Some(UNIX_EPOCH + Duration::from_secs(((days * 24 + hour) * 60 + minute) * 60 + second))
+}
+
+fn days_of_month(year: u64, month: u64) -> u64 {
+    let leap = year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+    match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// ------------------------------------------------------ version line
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum VersionLine {
+    Major,
+    Minor,
+}
+
+impl VersionLine {
+    // The line of the version like (Some(1), None) or (Some(1), Some(2)).
+    // A missing minor version counts as 0.
+    fn of(&self, version: &Version) -> (Option<u32>, Option<u32>) {
+        match self {
+            Major => (version.major, None),
+            Minor => (
+                version.major,
+                version.major.map(|_| version.minor.unwrap_or(0)),
+            ),
+        }
+    }
 }
 
 // ------------------------------------------------------ release type
@@ -254,22 +541,13 @@ pub enum ReleaseType {
     Snapshots,
 }
 
-impl ReleaseType {
-    pub fn from(args: &ArgMatches) -> Option<ReleaseType> {
-        if args.get_flag("releases") {
-            Some(Releases)
-        } else if args.get_flag("snapshots") {
-            Some(Snapshots)
-        } else {
-            None
-        }
-    }
-}
-
 // ------------------------------------------------------ version tests
 
 #[cfg(test)]
 mod version_tests {
+    use std::path::Path;
+    use std::str::FromStr;
+
     use crate::version::Version;
 
     #[test]
@@ -375,6 +653,38 @@ mod version_tests {
         );
     }
 
+    #[test]
+    fn order_by_components() {
+        let mut installed = Version::from_path(Path::new("installed/org/foo/1.0")).unwrap();
+        let cached = Version::from_path(Path::new("cached/org/foo/1.0")).unwrap();
+        installed.bytes = 42;
+        assert_eq!(installed, cached);
+        assert_eq!(std::cmp::Ordering::Equal, installed.cmp(&cached));
+    }
+
+    #[test]
+    fn opaque() {
+        let latest = Version::opaque("latest");
+        assert!(latest.is_opaque());
+        assert!(!latest.snapshot);
+        assert_eq!("latest", latest.to_string());
+
+        let master = Version::opaque("master-SNAPSHOT");
+        assert!(master.snapshot);
+        assert_eq!("master-SNAPSHOT", master.to_string());
+
+        let revision = Version::from_path(Path::new("org/foo/bar/${revision}")).unwrap();
+        assert!(revision.is_opaque());
+        assert_eq!("${revision}", revision.to_string());
+        assert!(!Version::from_path(Path::new("org/foo/bar/1.0"))
+            .unwrap()
+            .is_opaque());
+
+        // below all other versions, by name among themselves
+        assert!(latest < Version::parse("0.1").unwrap());
+        assert!(Version::opaque("a1b2c3") < latest);
+    }
+
     fn assert_version(
         version: &str,
         major: Option<u32>,
@@ -387,7 +697,7 @@ mod version_tests {
         assert_eq!(major, v.major);
         assert_eq!(minor, v.minor);
         assert_eq!(incremental, v.patch);
-        assert_eq!(qualifier.map(|s| String::from(s)), v.qualifier);
+        assert_eq!(qualifier.map(String::from), v.qualifier);
         assert_eq!(snapshot, v.snapshot);
     }
 }
@@ -396,8 +706,12 @@ mod version_tests {
 
 #[cfg(test)]
 mod version_range_tests {
-    use crate::version::VersionRange::{Exact, Latest, Oldest};
-    use crate::version::{Version, VersionRange};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use crate::version::VersionLine::{Major, Minor};
+    use crate::version::VersionRange::{Exact, Latest, LatestPer, Oldest, Since, Until};
+    use crate::version::{last_updated, Version, VersionRange};
+    use std::str::FromStr;
 
     #[test]
     fn invalid_version_range() {
@@ -413,6 +727,24 @@ mod version_range_tests {
         assert!(VersionRange::parse("...1").is_err());
         assert!(VersionRange::parse("1.1..").is_err());
         assert!(VersionRange::parse("..1.1").is_err());
+        assert!(VersionRange::parse("since:").is_err());
+        assert!(VersionRange::parse("since:2024").is_err());
+        assert!(VersionRange::parse("since:2024-13-01").is_err());
+        assert!(VersionRange::parse("since:2024-02-30").is_err());
+        assert!(VersionRange::parse("since:2023-02-29").is_err());
+        assert!(VersionRange::parse("since:2024-04-31").is_err());
+        assert!(VersionRange::parse("until:2023-13-01").is_err());
+        assert!(VersionRange::parse("until:2100-02-29").is_err());
+        assert!(VersionRange::parse("until:1969-12-31").is_err());
+        assert!(VersionRange::parse("age<").is_err());
+        assert!(VersionRange::parse("age<d").is_err());
+        assert!(VersionRange::parse("age<90").is_err());
+        assert!(VersionRange::parse("age>90h").is_err());
+        assert!(VersionRange::parse("age<999999999999999999d").is_err());
+        assert!(VersionRange::parse("latest-per:").is_err());
+        assert!(VersionRange::parse("latest-per:minor").is_err());
+        assert!(VersionRange::parse("latest-per:minor=0").is_err());
+        assert!(VersionRange::parse("latest-per:patch=1").is_err());
     }
 
     #[test]
@@ -435,6 +767,64 @@ mod version_range_tests {
         assert_eq!(Exact(version), VersionRange::parse("1.2.3").unwrap());
     }
 
+    #[test]
+    fn parse_latest_per() {
+        assert_eq!(
+            LatestPer(Major, 2),
+            VersionRange::parse("latest-per:major=2").unwrap()
+        );
+        assert_eq!(
+            LatestPer(Minor, 1),
+            VersionRange::parse("latest-per:minor=1").unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_dates() {
+        assert_eq!(
+            Since(UNIX_EPOCH + Duration::from_secs(86400)),
+            VersionRange::parse("since:1970-01-02").unwrap()
+        );
+        assert_eq!(
+            Until(UNIX_EPOCH + Duration::from_secs(1709251200)),
+            VersionRange::parse("until:2024-03-01").unwrap()
+        );
+        assert_eq!(
+            Since(UNIX_EPOCH + Duration::from_secs(1709164800)),
+            VersionRange::parse("since:2024-02-29").unwrap()
+        );
+        assert_eq!(
+            Some(UNIX_EPOCH + Duration::from_secs(1704110400)),
+            last_updated("<versioning><lastUpdated>20240101120000</lastUpdated></versioning>")
+        );
+    }
+
+    #[test]
+    fn parse_age() {
+        let day = Duration::from_secs(24 * 60 * 60);
+        let Since(since) = VersionRange::parse("age<90d").unwrap() else {
+            panic!("Expected since")
+        };
+        let age = SystemTime::now().duration_since(since).unwrap();
+        assert!(age >= 90 * day && age < 91 * day);
+        let Until(until) = VersionRange::parse("age>2w").unwrap() else {
+            panic!("Expected until")
+        };
+        let age = SystemTime::now().duration_since(until).unwrap();
+        assert!(age >= 14 * day && age < 15 * day);
+    }
+
+    #[test]
+    fn unknown_age() {
+        let version = Version::from_str("1.0").unwrap();
+        assert!(version.modified().is_none());
+        for range in ["age<90d", "age>90d", "since:2024-01-01", "until:2024-01-01"] {
+            let range = VersionRange::parse(range).unwrap();
+            assert!(!range.contains(&version));
+            assert!(!range.excludes(&version));
+        }
+    }
+
     #[test]
     fn select_latest() {
         let versions = vec![4, 3, 2, 1];
@@ -456,4 +846,30 @@ mod version_range_tests {
         assert_eq!(vec![4, 3, 2, 1], Oldest(4).select(&versions));
         assert_eq!(vec![4, 3, 2, 1], Oldest(5).select(&versions));
     }
+
+    #[test]
+    fn select_latest_per() {
+        let versions: Vec<Version> = ["3", "2.1.1", "2.1.0", "2.0.1", "2.0", "1.1", "1.0.0"]
+            .iter()
+            .map(|version| Version::parse(version).unwrap())
+            .collect();
+        let select = |version_range: VersionRange| -> Vec<String> {
+            version_range
+                .select_versions(&versions)
+                .iter()
+                .map(|version| version.to_string())
+                .collect()
+        };
+
+        assert_eq!(vec!["3", "2.1.1", "1.1"], select(LatestPer(Major, 1)));
+        assert_eq!(
+            vec!["3", "2.1.1", "2.1.0", "1.1", "1.0.0"],
+            select(LatestPer(Major, 2))
+        );
+        assert_eq!(
+            vec!["3", "2.1.1", "2.0.1", "1.1", "1.0.0"],
+            select(LatestPer(Minor, 1))
+        );
+        assert_eq!(vec!["3", "2.1.1"], select(Latest(2)));
+    }
 }
@@ -0,0 +1,173 @@
+mod fixture;
+
+use mcup::command::Command::Du;
+use mcup::command::DuOptions;
+use mcup::filter::{Filter, FilterBuilder};
+use mcup::output::summary;
+
+use crate::fixture::{assert_golden, Fixture, ATTACHMENT, JAR, METADATA, POM, STANDARD};
+
+#[test]
+fn selection() {
+    let fixture = Fixture::standard("du-selection");
+    let report = fixture.run(
+        Du(DuOptions {
+            apparent_size: true,
+            ..DuOptions::default()
+        }),
+        Filter::builder().groups("org.foo").artifacts("baz"),
+    );
+    assert_eq!(1, report.repository.groups.len());
+    assert_eq!(1, report.repository.artifacts);
+    assert_eq!(2, report.repository.versions);
+    assert!(report.paths.is_empty());
+    let baz = &report.repository.groups["org.foo"].artifacts["baz"];
+    assert!(baz.apparent_bytes > (2 * (POM + JAR) + 3 * ATTACHMENT) as u64);
+    // du doesn't touch the repository
+    assert_eq!(STANDARD.to_vec(), fixture.versions());
+}
+
+#[test]
+fn all() {
+    let fixture = Fixture::standard("du-all");
+    let options = DuOptions {
+        apparent_size: true,
+        ..DuOptions::default()
+    };
+    let without = fixture.run(Du(options.clone()), Filter::builder());
+    let with = fixture.run(
+        Du(DuOptions {
+            all: true,
+            ..options
+        }),
+        Filter::builder(),
+    );
+    assert_eq!(without.repository.versions, with.repository.versions);
+    // metadata of 'org.foo:bar' and the directory which is not a version
+    // (plus the directories themselves, which depend on the file system)
+    let foo = &with.repository.groups["org.foo"];
+    assert!(foo.unaccounted_apparent_bytes >= (METADATA + 50) as u64);
+    assert!(with.repository.apparent_bytes > without.repository.apparent_bytes);
+}
+
+#[test]
+fn lock_file() {
+    let fixture = Fixture::standard("du-lock-file");
+    let options = DuOptions {
+        apparent_size: true,
+        all: true,
+        ..DuOptions::default()
+    };
+    let without = fixture.run(Du(options.clone()), Filter::builder());
+    // the lock file of keep and rm is not part of the repository
+    fixture.file(".mcup.lock", 100);
+    let with = fixture.run(Du(options), Filter::builder());
+    assert_eq!(
+        without.repository.unaccounted_apparent_bytes,
+        with.repository.unaccounted_apparent_bytes
+    );
+}
+
+// ------------------------------------------------------ golden files
+
+#[test]
+fn groups() {
+    golden("du-groups", "g", false, Filter::builder());
+}
+
+#[test]
+fn artifacts_and_versions() {
+    golden("du-artifacts-versions", "av", false, Filter::builder());
+}
+
+#[test]
+fn hierarchy() {
+    golden("du-hierarchy", "gav", false, Filter::builder());
+}
+
+#[test]
+fn files() {
+    golden(
+        "du-files",
+        "gavf",
+        false,
+        Filter::builder().groups("org.foo"),
+    );
+}
+
+#[test]
+fn origins() {
+    golden("du-origins", "ogav", false, Filter::builder());
+}
+
+#[test]
+fn unaccounted() {
+    golden("du-unaccounted", "gav", true, Filter::builder());
+}
+
+#[test]
+fn snapshots() {
+    golden("du-snapshots", "gv", false, Filter::builder().snapshots());
+}
+
+#[test]
+fn empty() {
+    golden("du-empty", "gav", false, Filter::builder().groups("net"));
+}
+
+// Renders the du table of the standard fixture. The sizes include the
+// directories, which depend on the file system, so they're masked. The
+// selection and all tests check the sizes.
+fn golden(name: &str, output: &str, all: bool, filter: FilterBuilder) {
+    console::set_colors_enabled(false);
+    let fixture = Fixture::standard(name);
+    let report = fixture.run(
+        Du(DuOptions {
+            origins: output.contains('o'),
+            groups: output.contains('g'),
+            artifacts: output.contains('a'),
+            versions: output.contains('v'),
+            files: output.contains('f'),
+            apparent_size: true,
+            all,
+        }),
+        filter,
+    );
+    let mut out = Vec::new();
+    summary(
+        &mut out,
+        &report.command,
+        &report.repository,
+        &report.filter,
+        report.duration,
+    )
+    .unwrap();
+    assert_golden(name, &fixture.mask(out));
+}
+
+#[test]
+fn opaque() {
+    console::set_colors_enabled(false);
+    let fixture = Fixture::standard("du-opaque");
+    fixture.release("org.foo:bar:master-SNAPSHOT", "central");
+    let report = fixture.run(
+        Du(DuOptions {
+            versions: true,
+            ..DuOptions::default()
+        }),
+        Filter::builder().opaque(),
+    );
+    assert_eq!(1, report.repository.versions);
+    let mut out = Vec::new();
+    summary(
+        &mut out,
+        &report.command,
+        &report.repository,
+        &report.filter,
+        report.duration,
+    )
+    .unwrap();
+    assert!(String::from_utf8(out)
+        .unwrap()
+        .contains("org.foo:bar:master-SNAPSHOT (central, opaque)"));
+}
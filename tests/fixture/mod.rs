@@ -0,0 +1,371 @@
+// Shared by the integration tests. Not every test uses every function.
+#![allow(dead_code)]
+
+use std::env;
+use std::fs::{create_dir_all, read_to_string, remove_dir_all, write, File, OpenOptions};
+#[cfg(unix)]
+use std::mem;
+#[cfg(unix)]
+use std::os::fd::AsRawFd;
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use mcup::cleanup::{Cleanup, Report};
+use mcup::command::Command;
+use mcup::filter::FilterBuilder;
+use mcup::protect::Protection;
+use mcup::repo::Repository;
+use regex::Regex;
+use walkdir::WalkDir;
+
+// file sizes of the generated files
+pub const POM: usize = 100;
+pub const JAR: usize = 1000;
+pub const ATTACHMENT: usize = 500;
+pub const METADATA: usize = 200;
+
+const SNAPSHOT_TIMESTAMP: &str = "20240101.120000-1";
+
+// ------------------------------------------------------ fixture
+
+// A synthetic local repository in a temp directory. The directory is
+// removed when the fixture is dropped.
+pub struct Fixture {
+    pub path: PathBuf,
+}
+
+impl Fixture {
+    pub fn new(name: &str) -> Fixture {
+        let path = env::temp_dir().join(format!("mcup-{}-{}", name, std::process::id()));
+        if path.exists() {
+            remove_dir_all(&path).unwrap();
+        }
+        create_dir_all(&path).unwrap();
+        Fixture { path }
+    }
+
+    // The repository used by most tests:
+    //
+    //   com.acme:tool        3.0 (local), 3.1-SNAPSHOT (jboss-snapshots)
+    //   edu.odd:weird        1.0.0-Beta, 1.0.0.Final, 2 (central)
+    //   org.foo:bar          1.0, 1.1, 2.0 (central), 2.1-SNAPSHOT (local)
+    //   org.foo:baz          1.0 (central, sources, javadoc), 1.1 (central, sources)
+    //   org.foo.sub:qux      0.9 (jboss)
+    //
+    // plus metadata of 'org.foo:bar', a directory which is not a version and
+    // a leftover of a failed download.
+    pub fn standard(name: &str) -> Fixture {
+        let fixture = Fixture::new(name);
+        fixture
+            .release("com.acme:tool:3.0", "")
+            .snapshot("com.acme:tool:3.1-SNAPSHOT", "jboss-snapshots")
+            .release("edu.odd:weird:1.0.0-Beta", "central")
+            .release("edu.odd:weird:1.0.0.Final", "central")
+            .release("edu.odd:weird:2", "central")
+            .release("org.foo:bar:1.0", "central")
+            .release("org.foo:bar:1.1", "central")
+            .release("org.foo:bar:2.0", "central")
+            .snapshot("org.foo:bar:2.1-SNAPSHOT", "")
+            .release("org.foo:baz:1.0", "central")
+            .attachment("org.foo:baz:1.0", "sources")
+            .attachment("org.foo:baz:1.0", "javadoc")
+            .release("org.foo:baz:1.1", "central")
+            .attachment("org.foo:baz:1.1", "sources")
+            .release("org.foo.sub:qux:0.9", "jboss")
+            .file("org/foo/bar/maven-metadata-central.xml", METADATA)
+            .file("org/foo/bar/latest/README", 50)
+            .file("org/foo/bar/1.0/bar-1.0-sources.jar.lastUpdated", 30);
+        fixture
+    }
+
+    // A split repository with the same version in both trees:
+    //
+    //   installed/org/foo/bar/2.0
+    //   cached/org/foo/bar/1.0
+    //   cached/org/foo/bar/2.0
+    pub fn split(name: &str) -> Fixture {
+        let fixture = Fixture::new(name);
+        for (prefix, version) in [("installed", "2.0"), ("cached", "1.0"), ("cached", "2.0")] {
+            fixture.file(
+                &format!("{prefix}/org/foo/bar/{version}/bar-{version}.pom"),
+                POM,
+            );
+        }
+        fixture
+    }
+
+    // A release with a pom and a jar. 'repository' is the ID of the remote
+    // repository or empty for 'mvn install'.
+    pub fn release(&self, gav: &str, repository: &str) -> &Fixture {
+        let (dir, artifact_id, version) = self.version_dir(gav);
+        let files = [
+            format!("{artifact_id}-{version}.pom"),
+            format!("{artifact_id}-{version}.jar"),
+        ];
+        self.write_file(&dir.join(&files[0]), POM);
+        self.write_file(&dir.join(&files[1]), JAR);
+        remote_repositories(&dir, &files, repository);
+        self
+    }
+
+    // A snapshot with a pom, a jar and the snapshot metadata. Remote snapshots
+    // use timestamped file names.
+    pub fn snapshot(&self, gav: &str, repository: &str) -> &Fixture {
+        let (dir, artifact_id, version) = self.version_dir(gav);
+        let base_version = if repository.is_empty() {
+            version.clone()
+        } else {
+            format!(
+                "{}-{}",
+                version.trim_end_matches("-SNAPSHOT"),
+                SNAPSHOT_TIMESTAMP
+            )
+        };
+        let files = [
+            format!("{artifact_id}-{base_version}.pom"),
+            format!("{artifact_id}-{base_version}.jar"),
+        ];
+        self.write_file(&dir.join(&files[0]), POM);
+        self.write_file(&dir.join(&files[1]), JAR);
+        let metadata_id = if repository.is_empty() {
+            "local"
+        } else {
+            repository
+        };
+        self.write_file(
+            &dir.join(format!("maven-metadata-{metadata_id}.xml")),
+            METADATA,
+        );
+        remote_repositories(&dir, &files, repository);
+        self
+    }
+
+    // An attachment like 'sources' or 'javadoc'
+    pub fn attachment(&self, gav: &str, classifier: &str) -> &Fixture {
+        let (dir, artifact_id, version) = self.version_dir(gav);
+        self.write_file(
+            &dir.join(format!("{artifact_id}-{version}-{classifier}.jar")),
+            ATTACHMENT,
+        );
+        self
+    }
+
+    // Any file relative to the repository
+    pub fn file(&self, path: &str, size: usize) -> &Fixture {
+        self.write_file(&self.path.join(path), size);
+        self
+    }
+
+    // Any file relative to the repository with the given content
+    pub fn text(&self, path: &str, content: &str) -> &Fixture {
+        let path = self.path.join(path);
+        create_dir_all(path.parent().unwrap()).unwrap();
+        write(path, content).unwrap();
+        self
+    }
+
+    // Sets the modification time of the version directories. 'days' are
+    // counted since the epoch.
+    pub fn modified(&self, gavs: &[&str], days: u64) -> &Fixture {
+        let time = UNIX_EPOCH + Duration::from_secs(days * 24 * 60 * 60);
+        for gav in gavs {
+            let (dir, _, _) = self.version_dir(gav);
+            File::open(dir).unwrap().set_modified(time).unwrap();
+        }
+        self
+    }
+
+    // Sets the modification time of the version directories to 'days' ago
+    pub fn aged(&self, gavs: &[&str], days: u64) -> &Fixture {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        self.modified(gavs, now / (24 * 60 * 60) - days)
+    }
+
+    pub fn repository(&self) -> Repository {
+        Repository::open(self.path.clone())
+    }
+
+    // Runs the command without protection
+    pub fn run(&self, command: Command, filter: FilterBuilder) -> Report {
+        Cleanup::new(self.repository(), command)
+            .filter(
+                filter
+                    .protection(Protection { patterns: vec![] })
+                    .build()
+                    .unwrap(),
+            )
+            .run()
+            .unwrap()
+    }
+
+    // The versions left in the repository as 'groupId:artifactId:version'
+    pub fn versions(&self) -> Vec<String> {
+        let mut versions: Vec<String> = self
+            .files()
+            .iter()
+            .filter(|file| file.ends_with(".pom"))
+            .filter_map(|file| {
+                let path = Path::new(file);
+                let version_path = path.parent()?;
+                let artifact_path = version_path.parent()?;
+                let group_path = artifact_path.parent()?;
+                Some(format!(
+                    "{}:{}:{}",
+                    group_path.to_str()?.replace(MAIN_SEPARATOR, "."),
+                    artifact_path.file_name()?.to_str()?,
+                    version_path.file_name()?.to_str()?
+                ))
+            })
+            .collect();
+        versions.sort();
+        versions
+    }
+
+    // All files relative to the repository using '/' as separator
+    pub fn files(&self) -> Vec<String> {
+        let mut files: Vec<String> = WalkDir::new(&self.path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|dir_entry| dir_entry.file_type().is_file())
+            .filter_map(|dir_entry| {
+                dir_entry
+                    .path()
+                    .strip_prefix(&self.path)
+                    .ok()
+                    .and_then(Path::to_str)
+                    .map(|path| path.replace(MAIN_SEPARATOR, "/"))
+            })
+            .collect();
+        files.sort();
+        files
+    }
+
+    // Replaces the path of the fixture and the sizes in a table rendered by
+    // mcup::output. The sizes include the directories, which depend on the
+    // file system. The table stays aligned.
+    pub fn mask(&self, out: Vec<u8>) -> String {
+        let path = self.path.display().to_string();
+        let table = String::from_utf8(out).unwrap().replace(
+            path.as_str(),
+            format!("{:<width$}", "<repository>", width = path.len()).as_str(),
+        );
+        Regex::new(r"(?m)^([│┃]) *[0-9.]+ [kMG]?B ([│┃])")
+            .unwrap()
+            .replace_all(&table, format!("$1 {:>8} $2", "<size>").as_str())
+            .to_string()
+    }
+
+    pub fn exists(&self, path: &str) -> bool {
+        self.path.join(path).exists()
+    }
+
+    // Locks the file like a running Maven or Gradle build: 'FileChannel.lock()'
+    // takes a record lock (fcntl). The lock is released when the returned file
+    // is dropped, but also when this process closes any other descriptor of the file.
+    #[cfg(unix)]
+    pub fn lock(&self, path: &str) -> File {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(self.path.join(path))
+            .unwrap();
+        // SAFETY: flock is a plain C struct, all zeros is a valid value
+        let mut lock: libc::flock = unsafe { mem::zeroed() };
+        lock.l_type = libc::F_WRLCK as _;
+        lock.l_whence = libc::SEEK_SET as _;
+        // SAFETY: the file is open and the lock outlives the call
+        assert_eq!(0, unsafe {
+            libc::fcntl(file.as_raw_fd(), libc::F_SETLK, &lock)
+        });
+        file
+    }
+
+    fn version_dir(&self, gav: &str) -> (PathBuf, String, String) {
+        let coordinates: Vec<&str> = gav.split(':').collect();
+        let [group_id, artifact_id, version] = coordinates[..] else {
+            panic!("Illegal GAV: {gav}")
+        };
+        let dir = self
+            .path
+            .join(group_id.replace('.', "/"))
+            .join(artifact_id)
+            .join(version);
+        (dir, artifact_id.to_string(), version.to_string())
+    }
+
+    fn write_file(&self, path: &Path, size: usize) {
+        create_dir_all(path.parent().unwrap()).unwrap();
+        write(path, vec![b'x'; size]).unwrap();
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = remove_dir_all(&self.path);
+    }
+}
+
+fn remote_repositories(dir: &Path, files: &[String], repository: &str) {
+    let mut content = String::from(
+        "#NOTE: This is a Maven Resolver internal implementation file, its format can be changed without prior notice.\n#Mon Jan 01 12:00:00 CET 2024\n",
+    );
+    for file in files {
+        content.push_str(&format!("{file}>{repository}=\n"));
+    }
+    write(dir.join("_remote.repositories"), content).unwrap();
+}
+
+// ------------------------------------------------------ golden files
+
+// Compares 'actual' with 'tests/golden/<name>.txt'. Set 'UPDATE_GOLDEN' to
+// write the golden file instead.
+pub fn assert_golden(name: &str, actual: &str) {
+    let golden = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.txt"));
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        create_dir_all(golden.parent().unwrap()).unwrap();
+        write(&golden, actual).unwrap();
+    } else {
+        let expected = read_to_string(&golden)
+            .unwrap_or_else(|_| panic!("Missing golden file '{}'", golden.display()));
+        assert_eq!(
+            expected,
+            actual,
+            "table differs from '{}'",
+            golden.display()
+        );
+    }
+}
+
+// ------------------------------------------------------ standard versions
+
+// The versions of Fixture::standard()
+pub const STANDARD: [&str; 12] = [
+    "com.acme:tool:3.0",
+    "com.acme:tool:3.1-SNAPSHOT",
+    "edu.odd:weird:1.0.0-Beta",
+    "edu.odd:weird:1.0.0.Final",
+    "edu.odd:weird:2",
+    "org.foo.sub:qux:0.9",
+    "org.foo:bar:1.0",
+    "org.foo:bar:1.1",
+    "org.foo:bar:2.0",
+    "org.foo:bar:2.1-SNAPSHOT",
+    "org.foo:baz:1.0",
+    "org.foo:baz:1.1",
+];
+
+// The standard versions without 'removed'
+pub fn standard_without(removed: &[&str]) -> Vec<String> {
+    STANDARD
+        .iter()
+        .filter(|version| !removed.contains(version))
+        .map(|version| version.to_string())
+        .collect()
+}
@@ -0,0 +1,38 @@
+mod fixture;
+
+use mcup::cleanup::Cleanup;
+use mcup::command::Command::Remove;
+use mcup::filter::Filter;
+
+use crate::fixture::{Fixture, JAR, POM};
+
+const SHA1: &str = "0123456789abcdef0123456789abcdef01234567";
+
+// Other unix systems don't see the record locks of this process
+#[cfg(target_os = "linux")]
+#[test]
+fn running_build() {
+    let fixture = Fixture::new("gradle-running-build");
+    fixture
+        .file(
+            &format!("files-2.1/org.foo/bar/1.0/{SHA1}/bar-1.0.pom"),
+            POM,
+        )
+        .file(
+            &format!("files-2.1/org.foo/bar/1.0/{SHA1}/bar-1.0.jar"),
+            JAR,
+        )
+        .file("modules-2.lock", 0);
+    let lock = fixture.lock("modules-2.lock");
+    let cleanup = |fixture: &Fixture| {
+        Cleanup::new(fixture.repository(), Remove(false, false))
+            .filter(Filter::builder().artifacts("bar").build().unwrap())
+            .run()
+    };
+    assert!(cleanup(&fixture).is_err());
+    assert!(fixture.exists("files-2.1/org.foo/bar/1.0"));
+
+    drop(lock);
+    assert!(cleanup(&fixture).is_ok());
+    assert!(!fixture.exists("files-2.1/org.foo/bar/1.0"));
+}
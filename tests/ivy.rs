@@ -0,0 +1,46 @@
+mod fixture;
+
+use mcup::cleanup::Cleanup;
+use mcup::command::Command::Remove;
+use mcup::filter::Filter;
+use mcup::protect::{CoordinatePattern, Protection};
+use mcup::repo::Repository;
+
+use crate::fixture::{Fixture, JAR};
+
+const MODULE: &str = ".ivy2/cache/org.foo/bar";
+
+#[test]
+fn protected_revision() {
+    let fixture = Fixture::new("ivy-protection");
+    for revision in ["1.0-RC1", "1.1"] {
+        fixture
+            .text(
+                &format!("{MODULE}/ivy-{revision}.xml"),
+                &format!(
+                    r#"<ivy-module version="2.0"><info organisation="org.foo" module="bar" revision="{revision}"/></ivy-module>"#
+                ),
+            )
+            .file(&format!("{MODULE}/jars/bar-{revision}.jar"), JAR);
+    }
+    let report = Cleanup::new(
+        Repository::open(fixture.path.join(".ivy2/cache")),
+        Remove(false, false),
+    )
+    .filter(
+        Filter::builder()
+            .artifacts("bar")
+            .protection(Protection {
+                patterns: vec![CoordinatePattern::parse("org.foo:bar:1.0-RC1").unwrap()],
+            })
+            .build()
+            .unwrap(),
+    )
+    .run()
+    .unwrap();
+    assert_eq!(1, report.repository.protected);
+    assert!(fixture.exists(&format!("{MODULE}/ivy-1.0-RC1.xml")));
+    assert!(fixture.exists(&format!("{MODULE}/jars/bar-1.0-RC1.jar")));
+    assert!(!fixture.exists(&format!("{MODULE}/ivy-1.1.xml")));
+    assert!(!fixture.exists(&format!("{MODULE}/jars/bar-1.1.jar")));
+}
@@ -0,0 +1,246 @@
+mod fixture;
+
+use mcup::cleanup::Cleanup;
+use mcup::command::Command::Keep;
+use mcup::filter::Filter;
+
+use crate::fixture::{standard_without, Fixture, STANDARD};
+
+// keep selects what to keep and removes anything else
+
+#[test]
+fn groups_only() {
+    let fixture = Fixture::standard("keep-groups");
+    fixture.run(Keep(false, false), Filter::builder().groups("org.foo"));
+    assert_eq!(
+        standard_without(&[
+            "com.acme:tool:3.0",
+            "com.acme:tool:3.1-SNAPSHOT",
+            "edu.odd:weird:1.0.0-Beta",
+            "edu.odd:weird:1.0.0.Final",
+            "edu.odd:weird:2",
+        ]),
+        fixture.versions()
+    );
+    assert!(!fixture.exists("com/acme"));
+    assert!(!fixture.exists("edu/odd"));
+}
+
+#[test]
+fn groups_only_opaque() {
+    // opaque versions outside the groups are not removed either
+    let fixture = Fixture::standard("keep-groups-opaque");
+    fixture.release("com.acme:tool:latest", "central");
+    fixture.run(Keep(false, false), Filter::builder().groups("org.foo"));
+    assert!(!fixture.exists("com/acme/tool/3.0"));
+    assert!(fixture.exists("com/acme/tool/latest"));
+}
+
+#[test]
+fn artifacts_only() {
+    let fixture = Fixture::standard("keep-artifacts");
+    fixture.run(Keep(false, false), Filter::builder().artifacts("bar"));
+    assert_eq!(
+        vec![
+            "org.foo:bar:1.0",
+            "org.foo:bar:1.1",
+            "org.foo:bar:2.0",
+            "org.foo:bar:2.1-SNAPSHOT",
+        ],
+        fixture.versions()
+    );
+}
+
+#[test]
+fn versions_only() {
+    let fixture = Fixture::standard("keep-versions");
+    fixture.run(Keep(false, false), Filter::builder().versions("1.."));
+    assert_eq!(
+        vec![
+            "com.acme:tool:3.1-SNAPSHOT",
+            "edu.odd:weird:2",
+            "org.foo.sub:qux:0.9",
+            "org.foo:bar:2.1-SNAPSHOT",
+            "org.foo:baz:1.1",
+        ],
+        fixture.versions()
+    );
+}
+
+#[test]
+fn exact_version() {
+    let fixture = Fixture::standard("keep-exact");
+    fixture.run(Keep(false, false), Filter::builder().versions("1.0"));
+    assert_eq!(
+        vec!["org.foo:bar:1.0", "org.foo:baz:1.0"],
+        fixture.versions()
+    );
+}
+
+#[test]
+fn groups_and_versions() {
+    let fixture = Fixture::standard("keep-groups-versions");
+    fixture.run(
+        Keep(false, false),
+        Filter::builder().groups("org.foo").versions("2.."),
+    );
+    // nothing outside 'org.foo' is touched
+    assert_eq!(
+        standard_without(&["org.foo:bar:1.0", "org.foo:bar:1.1"]),
+        fixture.versions()
+    );
+}
+
+#[test]
+fn snapshots() {
+    let fixture = Fixture::standard("keep-snapshots");
+    fixture.run(Keep(false, false), Filter::builder().snapshots());
+    assert_eq!(
+        vec!["com.acme:tool:3.1-SNAPSHOT", "org.foo:bar:2.1-SNAPSHOT"],
+        fixture.versions()
+    );
+}
+
+#[test]
+fn origin() {
+    let fixture = Fixture::standard("keep-origin");
+    fixture.run(Keep(false, false), Filter::builder().origin("central"));
+    assert_eq!(
+        standard_without(&[
+            "com.acme:tool:3.0",
+            "com.acme:tool:3.1-SNAPSHOT",
+            "org.foo.sub:qux:0.9",
+            "org.foo:bar:2.1-SNAPSHOT",
+        ]),
+        fixture.versions()
+    );
+}
+
+#[test]
+fn repository_ids() {
+    let fixture = Fixture::standard("keep-repository-ids");
+    fixture.run(
+        Keep(false, false),
+        Filter::builder().repository_ids("jboss, jboss-snapshots"),
+    );
+    assert_eq!(
+        vec!["com.acme:tool:3.1-SNAPSHOT", "org.foo.sub:qux:0.9"],
+        fixture.versions()
+    );
+}
+
+#[test]
+fn classifiers() {
+    let fixture = Fixture::standard("keep-classifiers");
+    fixture.run(Keep(false, false), Filter::builder().classifiers("sources"));
+    assert_eq!(Vec::<String>::new(), fixture.versions());
+    assert!(fixture.exists("org/foo/baz/1.0/baz-1.0-sources.jar"));
+    assert!(fixture.exists("org/foo/baz/1.1/baz-1.1-sources.jar"));
+    assert!(!fixture.exists("org/foo/baz/1.0/baz-1.0-javadoc.jar"));
+    assert!(!fixture.exists("org/foo/bar/1.0/bar-1.0.jar"));
+}
+
+#[test]
+fn dry_run() {
+    let fixture = Fixture::standard("keep-dry-run");
+    let files = fixture.files();
+    let report = fixture.run(Keep(true, false), Filter::builder().versions("1.."));
+    assert_eq!(7, report.repository.versions);
+    assert_eq!(7, report.paths.len());
+    assert_eq!(files, fixture.files());
+}
+
+#[test]
+fn filter_required() {
+    let fixture = Fixture::standard("keep-filter-required");
+    assert!(Cleanup::new(fixture.repository(), Keep(false, false))
+        .run()
+        .is_err());
+    assert_eq!(STANDARD.to_vec(), fixture.versions());
+}
+
+#[test]
+fn opaque() {
+    let fixture = Fixture::standard("keep-opaque");
+    fixture
+        .release("org.foo:bar:latest", "central")
+        .release("com.acme:tool:latest", "central");
+    // '--opaque' limits keep to the opaque versions
+    fixture.run(
+        Keep(false, false),
+        Filter::builder().artifacts("bar").opaque(),
+    );
+    assert!(fixture.exists("org/foo/bar/latest"));
+    assert!(!fixture.exists("com/acme/tool/latest"));
+    let mut versions = [STANDARD.as_slice(), &["org.foo:bar:latest"]].concat();
+    versions.sort();
+    assert_eq!(versions, fixture.versions());
+    // opaque versions are not part of the version range
+    fixture.run(Keep(false, false), Filter::builder().versions("1.."));
+    assert!(fixture.exists("org/foo/bar/latest"));
+    fixture.run(Keep(false, false), Filter::builder().opaque());
+    assert!(!fixture.exists("org/foo/bar/latest"));
+    assert_eq!(
+        vec![
+            "com.acme:tool:3.1-SNAPSHOT",
+            "edu.odd:weird:2",
+            "org.foo.sub:qux:0.9",
+            "org.foo:bar:2.1-SNAPSHOT",
+            "org.foo:baz:1.1",
+        ],
+        fixture.versions()
+    );
+}
+
+#[test]
+fn age() {
+    let fixture = Fixture::standard("keep-age");
+    fixture
+        .aged(&STANDARD, 100)
+        .aged(&["org.foo:bar:2.0", "org.foo:bar:2.1-SNAPSHOT"], 30);
+    fixture.run(
+        Keep(false, false),
+        Filter::builder().groups("org.foo").versions("age<3m"),
+    );
+    assert_eq!(
+        vec![
+            "com.acme:tool:3.0",
+            "com.acme:tool:3.1-SNAPSHOT",
+            "edu.odd:weird:1.0.0-Beta",
+            "edu.odd:weird:1.0.0.Final",
+            "edu.odd:weird:2",
+            "org.foo:bar:2.0",
+            "org.foo:bar:2.1-SNAPSHOT",
+        ],
+        fixture.versions()
+    );
+}
+
+#[test]
+fn latest_per_minor() {
+    let fixture = Fixture::new("keep-latest-per-minor");
+    for version in ["1.0.0", "1.0.1", "1.1.0", "2.0", "2.0.1", "2.0.2"] {
+        fixture.release(&format!("org.foo:bar:{version}"), "central");
+    }
+    fixture.run(
+        Keep(false, false),
+        Filter::builder().versions("latest-per:minor=1"),
+    );
+    assert_eq!(
+        vec![
+            "org.foo:bar:1.0.1",
+            "org.foo:bar:1.1.0",
+            "org.foo:bar:2.0.2"
+        ],
+        fixture.versions()
+    );
+}
+
+#[test]
+fn split_versions() {
+    let fixture = Fixture::split("keep-split");
+    fixture.run(Keep(false, false), Filter::builder().versions("..1"));
+    assert!(!fixture.exists("installed/org/foo/bar/2.0"));
+    assert!(!fixture.exists("cached/org/foo/bar/2.0"));
+    assert!(fixture.exists("cached/org/foo/bar/1.0/bar-1.0.pom"));
+}
@@ -0,0 +1,325 @@
+mod fixture;
+
+use std::fs::write;
+
+use mcup::cleanup::Cleanup;
+use mcup::command::Command::Remove;
+use mcup::filter::Filter;
+use mcup::protect::{CoordinatePattern, Protection};
+
+use crate::fixture::{standard_without, Fixture, STANDARD};
+
+#[test]
+fn groups_only() {
+    let fixture = Fixture::standard("rm-groups");
+    let report = fixture.run(Remove(false, false), Filter::builder().groups("org.foo"));
+    assert_eq!(
+        vec![
+            "com.acme:tool:3.0",
+            "com.acme:tool:3.1-SNAPSHOT",
+            "edu.odd:weird:1.0.0-Beta",
+            "edu.odd:weird:1.0.0.Final",
+            "edu.odd:weird:2",
+        ],
+        fixture.versions()
+    );
+    assert_eq!(2, report.repository.groups.len());
+    assert_eq!(3, report.repository.artifacts);
+    assert_eq!(7, report.repository.versions);
+    // empty directories are pruned, the rest of the group stays
+    assert!(!fixture.exists("org/foo/sub"));
+    assert!(fixture.exists("org/foo/bar/maven-metadata-central.xml"));
+    assert!(fixture.exists("org/foo/bar/latest/README"));
+}
+
+#[test]
+fn artifacts_only() {
+    let fixture = Fixture::standard("rm-artifacts");
+    fixture.run(Remove(false, false), Filter::builder().artifacts("ba*"));
+    assert_eq!(
+        standard_without(&[
+            "org.foo:bar:1.0",
+            "org.foo:bar:1.1",
+            "org.foo:bar:2.0",
+            "org.foo:bar:2.1-SNAPSHOT",
+            "org.foo:baz:1.0",
+            "org.foo:baz:1.1",
+        ]),
+        fixture.versions()
+    );
+}
+
+#[test]
+fn versions_only() {
+    let fixture = Fixture::standard("rm-versions");
+    fixture.run(Remove(false, false), Filter::builder().versions("..1"));
+    assert_eq!(
+        standard_without(&[
+            "com.acme:tool:3.0",
+            "edu.odd:weird:1.0.0-Beta",
+            "org.foo.sub:qux:0.9",
+            "org.foo:bar:1.0",
+            "org.foo:baz:1.0",
+        ]),
+        fixture.versions()
+    );
+}
+
+#[test]
+fn exact_version() {
+    let fixture = Fixture::standard("rm-exact");
+    fixture.run(Remove(false, false), Filter::builder().versions("1.0"));
+    assert_eq!(
+        standard_without(&["org.foo:bar:1.0", "org.foo:baz:1.0"]),
+        fixture.versions()
+    );
+}
+
+#[test]
+fn groups_and_versions() {
+    let fixture = Fixture::standard("rm-groups-versions");
+    fixture.run(
+        Remove(false, false),
+        Filter::builder().groups("org.foo").versions("..1"),
+    );
+    assert_eq!(
+        standard_without(&["org.foo.sub:qux:0.9", "org.foo:bar:1.0", "org.foo:baz:1.0"]),
+        fixture.versions()
+    );
+}
+
+#[test]
+fn snapshots() {
+    let fixture = Fixture::standard("rm-snapshots");
+    fixture.run(Remove(false, false), Filter::builder().snapshots());
+    assert_eq!(
+        standard_without(&["com.acme:tool:3.1-SNAPSHOT", "org.foo:bar:2.1-SNAPSHOT"]),
+        fixture.versions()
+    );
+}
+
+#[test]
+fn releases_and_artifacts() {
+    let fixture = Fixture::standard("rm-releases-artifacts");
+    fixture.run(
+        Remove(false, false),
+        Filter::builder().releases().artifacts("bar"),
+    );
+    assert_eq!(
+        standard_without(&["org.foo:bar:1.0", "org.foo:bar:1.1", "org.foo:bar:2.0"]),
+        fixture.versions()
+    );
+}
+
+#[test]
+fn origin() {
+    let fixture = Fixture::standard("rm-origin");
+    fixture.run(Remove(false, false), Filter::builder().origin("local"));
+    assert_eq!(
+        standard_without(&["com.acme:tool:3.0", "org.foo:bar:2.1-SNAPSHOT"]),
+        fixture.versions()
+    );
+}
+
+#[test]
+fn repository_ids() {
+    let fixture = Fixture::standard("rm-repository-ids");
+    fixture.run(
+        Remove(false, false),
+        Filter::builder().repository_ids("jboss*"),
+    );
+    assert_eq!(
+        standard_without(&["com.acme:tool:3.1-SNAPSHOT", "org.foo.sub:qux:0.9"]),
+        fixture.versions()
+    );
+}
+
+#[test]
+fn classifiers() {
+    let fixture = Fixture::standard("rm-classifiers");
+    let report = fixture.run(
+        Remove(false, false),
+        Filter::builder().classifiers("sources"),
+    );
+    // the leftover of the failed download of the sources goes as well
+    assert_eq!(3, report.repository.files);
+    assert_eq!(STANDARD.to_vec(), fixture.versions());
+    assert!(!fixture.exists("org/foo/baz/1.0/baz-1.0-sources.jar"));
+    assert!(!fixture.exists("org/foo/baz/1.1/baz-1.1-sources.jar"));
+    assert!(!fixture.exists("org/foo/bar/1.0/bar-1.0-sources.jar.lastUpdated"));
+    assert!(fixture.exists("org/foo/baz/1.0/baz-1.0-javadoc.jar"));
+}
+
+#[test]
+fn attachments() {
+    let fixture = Fixture::standard("rm-attachments");
+    let report = fixture.run(
+        Remove(false, false),
+        Filter::builder().attachment("javadoc"),
+    );
+    assert_eq!(
+        vec![fixture.path.join("org/foo/baz/1.0/baz-1.0-javadoc.jar")],
+        report.paths
+    );
+    assert!(!fixture.exists("org/foo/baz/1.0/baz-1.0-javadoc.jar"));
+    assert!(fixture.exists("org/foo/baz/1.0/baz-1.0-sources.jar"));
+}
+
+#[test]
+fn dry_run() {
+    let fixture = Fixture::standard("rm-dry-run");
+    let files = fixture.files();
+    let report = fixture.run(Remove(true, false), Filter::builder().groups("org.foo.sub"));
+    assert_eq!(vec![fixture.path.join("org/foo/sub/qux/0.9")], report.paths);
+    assert_eq!(files, fixture.files());
+}
+
+#[test]
+fn protection() {
+    let fixture = Fixture::standard("rm-protection");
+    let report = Cleanup::new(fixture.repository(), Remove(false, false))
+        .filter(
+            Filter::builder()
+                .artifacts("bar")
+                .protection(Protection {
+                    patterns: vec![CoordinatePattern::parse("org.foo:bar:1.*").unwrap()],
+                })
+                .build()
+                .unwrap(),
+        )
+        .run()
+        .unwrap();
+    assert_eq!(2, report.repository.protected);
+    assert_eq!(
+        standard_without(&["org.foo:bar:2.0", "org.foo:bar:2.1-SNAPSHOT"]),
+        fixture.versions()
+    );
+}
+
+#[test]
+fn filter_required() {
+    let fixture = Fixture::standard("rm-filter-required");
+    assert!(Cleanup::new(fixture.repository(), Remove(false, false))
+        .run()
+        .is_err());
+    assert_eq!(STANDARD.to_vec(), fixture.versions());
+}
+
+#[test]
+fn opaque() {
+    let fixture = Fixture::standard("rm-opaque");
+    fixture
+        .release("org.foo:bar:latest", "central")
+        .release("org.foo:bar:${revision}", "");
+    let report = fixture.run(
+        Remove(false, false),
+        Filter::builder().artifacts("bar").opaque(),
+    );
+    assert_eq!(2, report.repository.versions);
+    assert_eq!(STANDARD.to_vec(), fixture.versions());
+}
+
+#[test]
+fn oldest_without_opaque() {
+    let fixture = Fixture::standard("rm-oldest-opaque");
+    fixture.release("org.foo:bar:latest", "central");
+    fixture.run(
+        Remove(false, false),
+        Filter::builder().artifacts("bar").versions("..1"),
+    );
+    assert!(fixture.exists("org/foo/bar/latest"));
+    assert!(!fixture.exists("org/foo/bar/1.0/bar-1.0.pom"));
+}
+
+// 2024-01-01 is day 19723 since the epoch
+#[test]
+fn since() {
+    let fixture = Fixture::standard("rm-since");
+    fixture
+        .modified(&STANDARD, 19000)
+        .modified(&["org.foo:bar:1.1", "com.acme:tool:3.0"], 19723);
+    fixture.run(
+        Remove(false, false),
+        Filter::builder().versions("since:2024-01-01"),
+    );
+    assert_eq!(
+        standard_without(&["com.acme:tool:3.0", "org.foo:bar:1.1"]),
+        fixture.versions()
+    );
+}
+
+#[test]
+fn age() {
+    let fixture = Fixture::standard("rm-age");
+    fixture
+        .aged(&STANDARD, 10)
+        .modified(&["org.foo:baz:1.0"], 0);
+    // the last update of the snapshot metadata counts, not the directory
+    write(
+        fixture
+            .path
+            .join("org/foo/bar/2.1-SNAPSHOT/maven-metadata-local.xml"),
+        "<metadata><versioning><lastUpdated>19700101000000</lastUpdated></versioning></metadata>",
+    )
+    .unwrap();
+    fixture.run(
+        Remove(false, false),
+        Filter::builder().groups("org.foo").versions("age>90d"),
+    );
+    assert_eq!(
+        standard_without(&["org.foo:bar:2.1-SNAPSHOT", "org.foo:baz:1.0"]),
+        fixture.versions()
+    );
+}
+
+#[test]
+fn latest_per_major() {
+    let fixture = Fixture::standard("rm-latest-per-major");
+    fixture.run(
+        Remove(false, false),
+        Filter::builder()
+            .groups("org.foo")
+            .versions("latest-per:major=1"),
+    );
+    assert_eq!(
+        standard_without(&[
+            "org.foo.sub:qux:0.9",
+            "org.foo:bar:1.1",
+            "org.foo:bar:2.1-SNAPSHOT",
+            "org.foo:baz:1.1",
+        ]),
+        fixture.versions()
+    );
+}
+
+#[test]
+fn split_versions() {
+    // the same version in both trees counts once
+    let fixture = Fixture::split("rm-split");
+    let report = fixture.run(Remove(false, false), Filter::builder().versions("2.."));
+    assert_eq!(3, report.repository.versions);
+    assert!(!fixture.exists("installed/org/foo/bar/2.0"));
+    assert!(!fixture.exists("cached/org/foo/bar/1.0"));
+    assert!(!fixture.exists("cached/org/foo/bar/2.0"));
+}
+
+// Other unix systems don't see the record locks of this process
+#[cfg(target_os = "linux")]
+#[test]
+fn running_build() {
+    let fixture = Fixture::standard("rm-running-build");
+    fixture.file(".locks/artifact~org.foo~bar~1.0.lock", 0);
+    let lock = fixture.lock(".locks/artifact~org.foo~bar~1.0.lock");
+    let cleanup = |fixture: &Fixture| {
+        Cleanup::new(fixture.repository(), Remove(false, false))
+            .filter(Filter::builder().artifacts("bar").build().unwrap())
+            .run()
+    };
+    assert!(cleanup(&fixture).is_err());
+    assert_eq!(STANDARD.to_vec(), fixture.versions());
+
+    // the lock file of a killed build is no sign of a running build
+    drop(lock);
+    assert!(cleanup(&fixture).is_ok());
+    assert!(!fixture.exists("org/foo/bar/1.0"));
+}
@@ -0,0 +1,44 @@
+mod fixture;
+
+use mcup::command::Command::Du;
+use mcup::command::DuOptions;
+use mcup::filter::Filter;
+use mcup::output::versions;
+use mcup::version::Version;
+
+use crate::fixture::{assert_golden, Fixture};
+
+#[test]
+fn order() {
+    golden("versions-bar", "org.foo", "bar");
+}
+
+#[test]
+fn qualifiers() {
+    golden("versions-weird", "edu.odd", "weird");
+}
+
+// Renders the versions of an artifact of the standard fixture plus some
+// opaque versions like 'mcup versions <groupId>:<artifactId>' does
+fn golden(name: &str, group_id: &str, artifact_id: &str) {
+    console::set_colors_enabled(false);
+    let fixture = Fixture::standard(name);
+    fixture
+        .release(format!("{group_id}:{artifact_id}:latest").as_str(), "")
+        .snapshot(
+            format!("{group_id}:{artifact_id}:main-SNAPSHOT").as_str(),
+            "",
+        );
+    let report = fixture.run(
+        Du(DuOptions::default()),
+        Filter::builder().groups(group_id).artifacts(artifact_id),
+    );
+    let selected: Vec<&Version> = report.repository.groups[group_id].artifacts[artifact_id]
+        .versions
+        .values()
+        .rev()
+        .collect();
+    let mut out = Vec::new();
+    versions(&mut out, group_id, artifact_id, &selected).unwrap();
+    assert_golden(name, &fixture.mask(out));
+}